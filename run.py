@@ -1,7 +0,0 @@
-#!/usr/bin/env python3
-
-import os
-import sys
-
-os.execvp("cargo", ["cargo", "run", "--bin"] + sys.argv[1:])
-# os.execvp("cargo", ["cargo", "test"])
\ No newline at end of file
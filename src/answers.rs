@@ -0,0 +1,260 @@
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::error::{column_of, AocError, AocResult};
+use crate::solution::{Answer, Registry};
+
+/// Known answers for one input file. A part left out of the manifest is not
+/// checked.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Expected {
+    pub file: String,
+    pub day: u8,
+    pub part1: Option<Answer>,
+    pub part2: Option<Answer>,
+}
+
+/// The `answers.toml` of one year, e.g.
+///
+/// ```toml
+/// ["day_1_1.txt"]
+/// part1 = 142
+/// part2 = 142
+/// ```
+///
+/// Only the subset of TOML needed for that is understood: one table per input
+/// file, named after it, holding integer or string answers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Manifest {
+    pub year: u16,
+    pub dir: PathBuf,
+    pub entries: Vec<Expected>,
+}
+
+impl Manifest {
+    pub fn load(year: u16, path: &Path) -> AocResult<Self> {
+        let text = fs::read_to_string(path)?;
+        let dir = path.parent().unwrap_or(Path::new("")).to_path_buf();
+
+        Self::parse(year, dir, &text)
+    }
+
+    pub fn parse(year: u16, dir: PathBuf, text: &str) -> AocResult<Self> {
+        let mut entries: Vec<Expected> = Vec::new();
+
+        for (index, raw) in text.lines().enumerate() {
+            let line_number = index + 1;
+            let line = strip_comment(raw).trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            if let Some(header) = line.strip_prefix('[') {
+                let name = header.strip_suffix(']').ok_or_else(|| {
+                    AocError::parse(line_number, raw.len() + 1, "expected `]`")
+                })?;
+                let name = name.trim();
+                let file = unquote(name).unwrap_or(name);
+                let day = day_of(file).ok_or_else(|| {
+                    AocError::parse(
+                        line_number,
+                        column_of(raw, file),
+                        format!("`{}` is not named `day_<day>_<n>.txt`", file),
+                    )
+                })?;
+
+                if entries.iter().any(|entry| entry.file == file) {
+                    return Err(AocError::parse(
+                        line_number,
+                        column_of(raw, file),
+                        format!("duplicate entry for `{}`", file),
+                    ));
+                }
+
+                entries.push(Expected {
+                    file: file.to_string(),
+                    day,
+                    part1: None,
+                    part2: None,
+                });
+                continue;
+            }
+
+            let (key, value) = line.split_once('=').ok_or_else(|| {
+                AocError::parse(
+                    line_number,
+                    column_of(raw, line),
+                    "expected `[\"<file>\"]` or `<part> = <answer>`",
+                )
+            })?;
+            let (key, value) = (key.trim(), value.trim());
+            let entry = entries.last_mut().ok_or_else(|| {
+                AocError::parse(
+                    line_number,
+                    column_of(raw, key),
+                    "answer outside of a `[\"<file>\"]` table",
+                )
+            })?;
+            let slot = match key {
+                "part1" => &mut entry.part1,
+                "part2" => &mut entry.part2,
+                _ => {
+                    return Err(AocError::parse(
+                        line_number,
+                        column_of(raw, key),
+                        format!("unknown key `{}`", key),
+                    ))
+                }
+            };
+
+            let answer = if let Some(text) = unquote(value) {
+                Answer::Text(text.to_string())
+            } else {
+                value.parse::<i128>().map(Answer::Number).map_err(|err| {
+                    AocError::parse(
+                        line_number,
+                        column_of(raw, value),
+                        format!("cannot parse `{}`: {}", value, err),
+                    )
+                })?
+            };
+            *slot = Some(answer);
+        }
+
+        Ok(Manifest { year, dir, entries })
+    }
+
+    /// Runs the registered solution for `expected` on its input file and
+    /// compares the answers. Only the parts listed in the manifest are run,
+    /// so an input that only has an answer for one part can still be
+    /// checked.
+    pub fn check(&self, registry: &Registry, expected: &Expected) -> Check {
+        let failures = match registry.get(self.year, expected.day) {
+            None => vec![Failure::NoSolution],
+            Some(puzzle) => {
+                match fs::read_to_string(self.dir.join(&expected.file))
+                    .map_err(AocError::from)
+                    .and_then(|input| puzzle.parse(&input))
+                {
+                    Err(err) => vec![Failure::Error(err)],
+                    Ok(parsed) => [(1, &expected.part1), (2, &expected.part2)]
+                        .into_iter()
+                        .filter_map(|(part, expected)| {
+                            let expected = expected.as_ref()?;
+                            let actual = if part == 1 {
+                                parsed.part1()
+                            } else {
+                                parsed.part2()
+                            };
+
+                            match actual {
+                                Err(err) => Some(Failure::Error(err)),
+                                Ok(actual) => {
+                                    (*expected != actual).then(|| {
+                                        Failure::Mismatch {
+                                            part,
+                                            expected: expected.clone(),
+                                            actual,
+                                        }
+                                    })
+                                }
+                            }
+                        })
+                        .collect(),
+                }
+            }
+        };
+
+        Check {
+            year: self.year,
+            day: expected.day,
+            file: expected.file.clone(),
+            failures,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum Failure {
+    NoSolution,
+    Error(AocError),
+    Mismatch {
+        part: u8,
+        expected: Answer,
+        actual: Answer,
+    },
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Failure::NoSolution => write!(f, "no registered solution"),
+            Failure::Error(err) => write!(f, "{}", err),
+            Failure::Mismatch {
+                part,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "part {}: expected {}, got {}",
+                part, expected, actual
+            ),
+        }
+    }
+}
+
+/// Outcome of checking one manifest entry.
+#[derive(Debug)]
+pub struct Check {
+    pub year: u16,
+    pub day: u8,
+    pub file: String,
+    pub failures: Vec<Failure>,
+}
+
+impl Check {
+    pub fn passed(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
+
+impl fmt::Display for Check {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} day {} ({}): ", self.year, self.day, self.file)?;
+
+        if self.passed() {
+            return write!(f, "ok");
+        }
+
+        let failures: Vec<String> =
+            self.failures.iter().map(ToString::to_string).collect();
+        write!(f, "{}", failures.join("; "))
+    }
+}
+
+fn strip_comment(line: &str) -> &str {
+    let mut in_string = false;
+
+    for (index, c) in line.char_indices() {
+        match c {
+            '"' => in_string = !in_string,
+            '#' if !in_string => return &line[..index],
+            _ => {}
+        }
+    }
+
+    line
+}
+
+fn unquote(value: &str) -> Option<&str> {
+    value.strip_prefix('"')?.strip_suffix('"')
+}
+
+/// Day number of an input file named `day_<day>_<n>.txt`.
+fn day_of(file: &str) -> Option<u8> {
+    let rest = file.strip_prefix("day_")?.strip_suffix(".txt")?;
+    let (day, _) = rest.split_once('_')?;
+
+    day.parse().ok()
+}
@@ -0,0 +1,171 @@
+use std::fmt::Write;
+use std::time::{Duration, Instant};
+
+use crate::error::AocResult;
+use crate::solution::Puzzle;
+
+/// Summary of the samples taken for one stage of a puzzle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timings {
+    pub min: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+}
+
+impl Timings {
+    /// Summarizes `samples`, which must not be empty.
+    pub fn from_samples(samples: &mut [Duration]) -> Self {
+        samples.sort_unstable();
+
+        let len = samples.len();
+        // The two middle samples coincide when `len` is odd
+        let median = (samples[(len - 1) / 2] + samples[len / 2]) / 2;
+        // Nearest-rank percentile
+        let p95 = samples[(len * 95).div_ceil(100) - 1];
+
+        Timings {
+            min: samples[0],
+            median,
+            p95,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BenchResult {
+    pub year: u16,
+    pub day: u8,
+    pub runs: usize,
+    pub parse: Timings,
+    pub part1: Timings,
+    pub part2: Timings,
+}
+
+impl BenchResult {
+    pub fn stages(&self) -> [(&'static str, &Timings); 3] {
+        [
+            ("parse", &self.parse),
+            ("part1", &self.part1),
+            ("part2", &self.part2),
+        ]
+    }
+}
+
+/// Parses and solves `input` `runs` times, timing parsing and each part
+/// separately.
+pub fn bench(
+    year: u16,
+    day: u8,
+    puzzle: &dyn Puzzle,
+    input: &str,
+    runs: usize,
+) -> AocResult<BenchResult> {
+    let mut parse = Vec::with_capacity(runs);
+    let mut part1 = Vec::with_capacity(runs);
+    let mut part2 = Vec::with_capacity(runs);
+
+    for _ in 0..runs.max(1) {
+        let start_time = Instant::now();
+        let parsed = puzzle.parse(input)?;
+        parse.push(start_time.elapsed());
+
+        let start_time = Instant::now();
+        parsed.part1()?;
+        part1.push(start_time.elapsed());
+
+        let start_time = Instant::now();
+        parsed.part2()?;
+        part2.push(start_time.elapsed());
+    }
+
+    Ok(BenchResult {
+        year,
+        day,
+        runs: parse.len(),
+        parse: Timings::from_samples(&mut parse),
+        part1: Timings::from_samples(&mut part1),
+        part2: Timings::from_samples(&mut part2),
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Markdown,
+    Json,
+}
+
+impl Format {
+    pub fn render(self, results: &[BenchResult]) -> String {
+        match self {
+            Format::Markdown => to_markdown(results),
+            Format::Json => to_json(results),
+        }
+    }
+}
+
+/// One row per stage of every result, with times in microseconds.
+pub fn to_markdown(results: &[BenchResult]) -> String {
+    let mut table = String::from(
+        "| year | day | stage | runs | min (µs) | median (µs) | p95 (µs) |\n\
+         |-----:|----:|:------|-----:|---------:|------------:|---------:|\n",
+    );
+
+    for result in results {
+        for (stage, timings) in result.stages() {
+            let _ = writeln!(
+                table,
+                "| {} | {} | {} | {} | {:.1} | {:.1} | {:.1} |",
+                result.year,
+                result.day,
+                stage,
+                result.runs,
+                micros(timings.min),
+                micros(timings.median),
+                micros(timings.p95),
+            );
+        }
+    }
+
+    table
+}
+
+/// A JSON array with one object per result, with times in nanoseconds.
+pub fn to_json(results: &[BenchResult]) -> String {
+    let entries: Vec<String> = results
+        .iter()
+        .map(|result| {
+            let stages: Vec<String> = result
+                .stages()
+                .iter()
+                .map(|(stage, timings)| {
+                    format!(
+                        "\"{}\": {{\"min_ns\": {}, \"median_ns\": {}, \
+                         \"p95_ns\": {}}}",
+                        stage,
+                        timings.min.as_nanos(),
+                        timings.median.as_nanos(),
+                        timings.p95.as_nanos(),
+                    )
+                })
+                .collect();
+
+            format!(
+                "  {{\"year\": {}, \"day\": {}, \"runs\": {}, {}}}",
+                result.year,
+                result.day,
+                result.runs,
+                stages.join(", ")
+            )
+        })
+        .collect();
+
+    if entries.is_empty() {
+        return "[]".to_string();
+    }
+
+    format!("[\n{}\n]", entries.join(",\n"))
+}
+
+fn micros(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1e6
+}
@@ -1,5 +1,21 @@
-use advent_of_code::runner;
+use std::{env, process::ExitCode};
 
-fn main() {
-    runner(|file_path: &str| {});
+use advent_of_code::{cli, registry};
+
+fn main() -> ExitCode {
+    let command = match cli::parse_args(env::args().skip(1)) {
+        Ok(command) => command,
+        Err(err) => {
+            eprintln!("error: {}\n\n{}", err, cli::USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match cli::execute(&command, &registry()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            ExitCode::FAILURE
+        }
+    }
 }
@@ -0,0 +1,409 @@
+use std::fmt;
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+use crate::answers::Manifest;
+use crate::bench::{bench, Format};
+use crate::error::AocResult;
+use crate::runner;
+use crate::solution::{Answer, Registry};
+use crate::trace;
+
+pub const USAGE: &str = "\
+Usage:
+    main run --year <year> --day <day> [--part <1|2>] [--input <path|->]
+             [--trace]
+    main list
+    main all [--year <year>] [--input <n>]
+    main verify [--year <year>] [--day <day>]
+    main bench [--year <year>] [--day <day>] [--runs <n>] [--input <n>]
+               [--format <markdown|json>]
+
+Options:
+    -y, --year   puzzle year (`run` defaults to 2023)
+    -d, --day    puzzle day
+    -p, --part   only run the given part
+    -i, --input  input file; a bare file name is looked up in input/y<year>/,
+                 `-` reads from stdin (defaults to day_<day>_1.txt);
+                 `all` and `bench` take a number n and read every
+                 day's day_<day>_<n>.txt instead
+    -n, --runs   number of timed runs per puzzle (defaults to 10)
+    -f, --format bench report format (defaults to markdown)
+    -t, --trace  print the solver's diagnostics to stderr while running";
+
+const DEFAULT_YEAR: u16 = 2023;
+const DEFAULT_RUNS: usize = 10;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Input {
+    Path(PathBuf),
+    Stdin,
+}
+
+impl Input {
+    pub fn default_for(year: u16, day: u8) -> Self {
+        Input::numbered(year, day, 1)
+    }
+
+    /// The `n`th input file of a day, `day_<day>_<n>.txt`.
+    pub fn numbered(year: u16, day: u8, n: u8) -> Self {
+        Input::Path(input_dir(year).join(format!("day_{}_{}.txt", day, n)))
+    }
+
+    fn from_arg(year: u16, value: &str) -> Self {
+        if value == "-" {
+            return Input::Stdin;
+        }
+
+        let path = Path::new(value);
+
+        if path.components().count() == 1 {
+            Input::Path(input_dir(year).join(path))
+        } else {
+            Input::Path(path.to_path_buf())
+        }
+    }
+
+    pub fn read(&self) -> AocResult<String> {
+        match self {
+            Input::Path(path) => Ok(fs::read_to_string(path)?),
+            Input::Stdin => {
+                let mut input = String::new();
+                io::stdin().read_to_string(&mut input)?;
+                Ok(input)
+            }
+        }
+    }
+}
+
+impl fmt::Display for Input {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Input::Path(path) => write!(f, "{}", path.display()),
+            Input::Stdin => write!(f, "<stdin>"),
+        }
+    }
+}
+
+fn input_dir(year: u16) -> PathBuf {
+    PathBuf::from(format!("input/y{}", year))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    Run {
+        year: u16,
+        day: u8,
+        part: Option<u8>,
+        input: Input,
+        trace: bool,
+    },
+    List,
+    All {
+        year: Option<u16>,
+        input: u8,
+    },
+    Verify {
+        year: Option<u16>,
+        day: Option<u8>,
+    },
+    Bench {
+        year: Option<u16>,
+        day: Option<u8>,
+        runs: usize,
+        format: Format,
+        input: u8,
+    },
+    Help,
+}
+
+pub fn parse_args<I, S>(args: I) -> Result<Command, String>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let args: Vec<String> =
+        args.into_iter().map(|s| s.as_ref().to_string()).collect();
+    let Some((command, rest)) = args.split_first() else {
+        return Ok(Command::Help);
+    };
+
+    let mut year = None;
+    let mut day = None;
+    let mut part = None;
+    let mut input = None;
+    let mut runs = DEFAULT_RUNS;
+    let mut format = Format::Markdown;
+    let mut trace = false;
+
+    let mut options = rest.iter();
+    while let Some(option) = options.next() {
+        if option == "-h" || option == "--help" {
+            return Ok(Command::Help);
+        }
+        if option == "-t" || option == "--trace" {
+            trace = true;
+            continue;
+        }
+
+        let value = options
+            .next()
+            .ok_or_else(|| format!("missing value for `{}`", option))?;
+
+        match option.as_str() {
+            "-y" | "--year" => year = Some(parse_number(option, value)?),
+            "-d" | "--day" => day = Some(parse_number(option, value)?),
+            "-p" | "--part" => match value.as_str() {
+                "1" => part = Some(1),
+                "2" => part = Some(2),
+                _ => {
+                    return Err(format!("part must be 1 or 2, got `{}`", value))
+                }
+            },
+            "-i" | "--input" => input = Some(value.clone()),
+            "-n" | "--runs" => match parse_number(option, value)? {
+                0 => return Err("`--runs` must be at least 1".to_string()),
+                value => runs = value,
+            },
+            "-f" | "--format" => match value.as_str() {
+                "markdown" => format = Format::Markdown,
+                "json" => format = Format::Json,
+                _ => {
+                    return Err(format!(
+                        "format must be markdown or json, got `{}`",
+                        value
+                    ))
+                }
+            },
+            _ => return Err(format!("unknown option `{}`", option)),
+        }
+    }
+
+    match command.as_str() {
+        "run" => {
+            let year = year.unwrap_or(DEFAULT_YEAR);
+            let day = day.ok_or("`run` requires --day")?;
+            let input = input.map_or_else(
+                || Input::default_for(year, day),
+                |value| Input::from_arg(year, &value),
+            );
+
+            Ok(Command::Run {
+                year,
+                day,
+                part,
+                input,
+                trace,
+            })
+        }
+        "list" => Ok(Command::List),
+        "all" => Ok(Command::All {
+            year,
+            input: input_number(input)?,
+        }),
+        "verify" => Ok(Command::Verify { year, day }),
+        "bench" => Ok(Command::Bench {
+            year,
+            day,
+            runs,
+            format,
+            input: input_number(input)?,
+        }),
+        "-h" | "--help" | "help" => Ok(Command::Help),
+        _ => Err(format!("unknown command `{}`", command)),
+    }
+}
+
+/// The input file number `all` and `bench` read for every day.
+fn input_number(input: Option<String>) -> Result<u8, String> {
+    match input {
+        None => Ok(1),
+        Some(value) => match parse_number("--input", &value)? {
+            0 => Err("`--input` must be at least 1".to_string()),
+            n => Ok(n),
+        },
+    }
+}
+
+fn parse_number<T: std::str::FromStr>(
+    option: &str,
+    value: &str,
+) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("invalid value `{}` for `{}`", value, option))
+}
+
+pub fn execute(command: &Command, registry: &Registry) -> Result<(), String> {
+    match command {
+        Command::Run {
+            year,
+            day,
+            part,
+            input,
+            trace,
+        } => {
+            let puzzle = registry.get(*year, *day).ok_or_else(|| {
+                format!("no solution for {} day {}", year, day)
+            })?;
+            let text =
+                input.read().map_err(|err| format!("{}: {}", input, err))?;
+            let run = || runner(puzzle, &text, *part);
+            let answers = if *trace {
+                trace::with_sink(|line| eprintln!("{}", line), run)
+            } else {
+                run()
+            }
+            .map_err(|err| format!("{}: {}", input, err))?;
+
+            if let (Some(part), [(_, answer)]) = (part, answers.as_slice()) {
+                if *answer == Answer::Unsolved {
+                    return Err(format!(
+                        "{} day {} part {} is not solved yet",
+                        year, day, part
+                    ));
+                }
+                println!("{}", answer);
+            } else {
+                for (part, answer) in answers {
+                    println!("Part {}: {}", part, answer);
+                }
+            }
+
+            Ok(())
+        }
+        Command::List => {
+            for (year, day, _) in registry.iter() {
+                println!("{} day {}", year, day);
+            }
+
+            Ok(())
+        }
+        Command::All { year, input } => {
+            let mut failures = 0;
+
+            for (puzzle_year, day, puzzle) in registry.iter() {
+                if year.is_some_and(|year| year != puzzle_year) {
+                    continue;
+                }
+
+                let input = Input::numbered(puzzle_year, day, *input);
+                let result =
+                    input.read().and_then(|text| runner(puzzle, &text, None));
+
+                match result {
+                    Ok(answers) => {
+                        let answers: Vec<String> = answers
+                            .iter()
+                            .map(|(_, answer)| answer.to_string())
+                            .collect();
+                        println!(
+                            "{} day {}: {}",
+                            puzzle_year,
+                            day,
+                            answers.join(", ")
+                        );
+                    }
+                    Err(err) => {
+                        failures += 1;
+                        eprintln!(
+                            "{} day {}: {}: {}",
+                            puzzle_year, day, input, err
+                        );
+                    }
+                }
+            }
+
+            if failures > 0 {
+                return Err(format!("{} puzzle(s) failed", failures));
+            }
+
+            Ok(())
+        }
+        Command::Verify { year, day } => {
+            let mut failures = 0;
+
+            for puzzle_year in registry.years() {
+                if year.is_some_and(|year| year != puzzle_year) {
+                    continue;
+                }
+
+                let path = input_dir(puzzle_year).join("answers.toml");
+                let manifest = match Manifest::load(puzzle_year, &path) {
+                    Ok(manifest) => manifest,
+                    Err(err) => {
+                        failures += 1;
+                        eprintln!("{}: {}", path.display(), err);
+                        continue;
+                    }
+                };
+
+                for expected in &manifest.entries {
+                    if day.is_some_and(|day| day != expected.day) {
+                        continue;
+                    }
+
+                    let check = manifest.check(registry, expected);
+                    if !check.passed() {
+                        failures += 1;
+                    }
+                    println!("{}", check);
+                }
+            }
+
+            if failures > 0 {
+                return Err(format!("{} check(s) failed", failures));
+            }
+
+            Ok(())
+        }
+        Command::Bench {
+            year,
+            day,
+            runs,
+            format,
+            input,
+        } => {
+            let mut results = Vec::new();
+            let mut failures = 0;
+
+            for (puzzle_year, puzzle_day, puzzle) in registry.iter() {
+                if year.is_some_and(|year| year != puzzle_year)
+                    || day.is_some_and(|day| day != puzzle_day)
+                {
+                    continue;
+                }
+
+                let input = Input::numbered(puzzle_year, puzzle_day, *input);
+                let result = input.read().and_then(|text| {
+                    bench(puzzle_year, puzzle_day, puzzle, &text, *runs)
+                });
+
+                match result {
+                    Ok(result) => results.push(result),
+                    Err(err) => {
+                        failures += 1;
+                        eprintln!(
+                            "{} day {}: {}: {}",
+                            puzzle_year, puzzle_day, input, err
+                        );
+                    }
+                }
+            }
+
+            println!("{}", format.render(&results).trim_end());
+
+            if failures > 0 {
+                return Err(format!("{} puzzle(s) failed", failures));
+            }
+
+            Ok(())
+        }
+        Command::Help => {
+            println!("{}", USAGE);
+
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::str::FromStr;
+
+#[derive(Debug)]
+pub enum AocError {
+    Io(io::Error),
+    /// Malformed input, located by its 1-based line and column.
+    Parse {
+        line: usize,
+        column: usize,
+        message: String,
+    },
+    /// The input lacks the marker a traversal starts from, e.g. `S`.
+    MissingStart(char),
+    /// The input parsed fine but has no answer.
+    Unsolvable(String),
+}
+
+pub type AocResult<T> = Result<T, AocError>;
+
+impl AocError {
+    pub fn parse(
+        line: usize,
+        column: usize,
+        message: impl Into<String>,
+    ) -> Self {
+        AocError::Parse {
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+
+    pub fn unsolvable(reason: impl Into<String>) -> Self {
+        AocError::Unsolvable(reason.into())
+    }
+}
+
+impl fmt::Display for AocError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AocError::Io(err) => write!(f, "{}", err),
+            AocError::Parse {
+                line,
+                column,
+                message,
+            } => write!(f, "line {}, column {}: {}", line, column, message),
+            AocError::MissingStart(marker) => {
+                write!(f, "no start marker `{}` in input", marker)
+            }
+            AocError::Unsolvable(reason) => {
+                write!(f, "input has no solution: {}", reason)
+            }
+        }
+    }
+}
+
+impl Error for AocError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            AocError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for AocError {
+    fn from(err: io::Error) -> Self {
+        AocError::Io(err)
+    }
+}
+
+/// 1-based column of `token` within `line`; `token` must be a subslice of
+/// `line`, as returned by `split`, `trim` and friends.
+pub fn column_of(line: &str, token: &str) -> usize {
+    (token.as_ptr() as usize).saturating_sub(line.as_ptr() as usize) + 1
+}
+
+/// Parses `token`, a subslice of `line`, reporting where it sits in the input
+/// when it is not a valid `T`.
+pub fn parse_token<T>(
+    line_number: usize,
+    line: &str,
+    token: &str,
+) -> AocResult<T>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    token.parse().map_err(|err| {
+        AocError::parse(
+            line_number,
+            column_of(line, token),
+            format!("cannot parse `{}`: {}", token, err),
+        )
+    })
+}
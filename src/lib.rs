@@ -1,28 +1,37 @@
-use std::{env, ffi::OsStr, path::PathBuf, time::Instant};
+use error::AocResult;
+use solution::{Answer, Puzzle, Registry};
 
+pub mod answers;
+pub mod bench;
+pub mod cli;
+pub mod error;
+pub mod solution;
+pub mod trace;
+pub mod utils;
 pub mod y2022;
 pub mod y2023;
-pub mod utils;
 
-pub fn runner(f: impl Fn(&str)) {
-    let start_time = Instant::now();
-    let file_path = format!("input/y2023/{}", file_name());
+pub fn registry() -> Registry {
+    let mut registry = Registry::new();
+    y2022::register(&mut registry);
+    y2023::register(&mut registry);
+    registry
+}
 
-    f(&file_path);
+/// Runs one or both parts of `puzzle` on the `input` text, returning each
+/// answer alongside its part number.
+pub fn runner(
+    puzzle: &dyn Puzzle,
+    input: &str,
+    part: Option<u8>,
+) -> AocResult<Vec<(u8, Answer)>> {
+    let parsed = puzzle.parse(input)?;
 
-    let elapsed_time = start_time.elapsed();
-    println!("Elapsed time: {:.2?}", elapsed_time.as_secs_f64());
-}
+    let answers = match part {
+        Some(1) => vec![(1, parsed.part1()?)],
+        Some(_) => vec![(2, parsed.part2()?)],
+        None => vec![(1, parsed.part1()?), (2, parsed.part2()?)],
+    };
 
-pub fn file_name() -> String {
-    env::args()
-        .skip(1)
-        .next()
-        .map(PathBuf::from)
-        .and_then(|path| {
-            path.file_name()
-                .map(OsStr::to_string_lossy)
-                .map(|s| s.into_owned())
-        })
-        .expect("file not found")
+    Ok(answers)
 }
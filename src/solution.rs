@@ -0,0 +1,170 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::error::AocResult;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Number(i128),
+    Text(String),
+    Unsolved,
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Number(value) => write!(f, "{}", value),
+            Answer::Text(value) => write!(f, "{}", value),
+            Answer::Unsolved => write!(f, "unsolved"),
+        }
+    }
+}
+
+macro_rules! impl_answer_from_number {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(value: $t) -> Self {
+                    Answer::Number(i128::from(value))
+                }
+            }
+        )*
+    };
+}
+
+impl_answer_from_number!(i32, i64, u32, u64);
+
+/// Values that do not fit an `i128` are kept exactly as text.
+macro_rules! impl_answer_from_wide_number {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(value: $t) -> Self {
+                    match i128::try_from(value) {
+                        Ok(value) => Answer::Number(value),
+                        Err(_) => Answer::Text(value.to_string()),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_answer_from_wide_number!(u128, usize);
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Answer::Text(value.to_string())
+    }
+}
+
+/// A puzzle solution split into parsing and the two parts, so every day can
+/// be driven the same way regardless of what its parsed input looks like.
+pub trait Solution {
+    type Input;
+
+    /// Parses the full puzzle input text.
+    fn parse(&self, input: &str) -> AocResult<Self::Input>;
+
+    fn part1(&self, input: &Self::Input) -> AocResult<Answer>;
+
+    fn part2(&self, _input: &Self::Input) -> AocResult<Answer> {
+        Ok(Answer::Unsolved)
+    }
+}
+
+/// Parsed input bound to the solution that produced it.
+pub trait Parsed {
+    fn part1(&self) -> AocResult<Answer>;
+    fn part2(&self) -> AocResult<Answer>;
+}
+
+/// Object-safe view of a [`Solution`], used by the [`Registry`] to hold days
+/// with different input types side by side.
+pub trait Puzzle: Sync {
+    fn parse<'a>(&'a self, input: &str) -> AocResult<Box<dyn Parsed + 'a>>;
+
+    fn run(&self, input: &str) -> AocResult<(Answer, Answer)> {
+        let parsed = self.parse(input)?;
+        Ok((parsed.part1()?, parsed.part2()?))
+    }
+}
+
+struct Prepared<'a, S: Solution> {
+    solution: &'a S,
+    input: S::Input,
+}
+
+impl<S: Solution> Parsed for Prepared<'_, S> {
+    fn part1(&self) -> AocResult<Answer> {
+        self.solution.part1(&self.input)
+    }
+
+    fn part2(&self) -> AocResult<Answer> {
+        self.solution.part2(&self.input)
+    }
+}
+
+impl<S: Solution + Sync> Puzzle for S {
+    fn parse<'a>(&'a self, input: &str) -> AocResult<Box<dyn Parsed + 'a>> {
+        let input = Solution::parse(self, input)?;
+        Ok(Box::new(Prepared {
+            solution: self,
+            input,
+        }))
+    }
+}
+
+#[derive(Default)]
+pub struct Registry {
+    solutions: BTreeMap<(u16, u8), &'static dyn Puzzle>,
+}
+
+impl Registry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn register(
+        &mut self,
+        year: u16,
+        day: u8,
+        puzzle: &'static dyn Puzzle,
+    ) {
+        self.solutions.insert((year, day), puzzle);
+    }
+
+    pub fn get(&self, year: u16, day: u8) -> Option<&'static dyn Puzzle> {
+        self.solutions.get(&(year, day)).copied()
+    }
+
+    pub fn years(&self) -> Vec<u16> {
+        let mut years: Vec<u16> =
+            self.solutions.keys().map(|&(year, _)| year).collect();
+        years.dedup();
+        years
+    }
+
+    /// Iterates over every registered puzzle ordered by year, then day.
+    pub fn iter(
+        &self,
+    ) -> impl Iterator<Item = (u16, u8, &'static dyn Puzzle)> + '_ {
+        self.solutions
+            .iter()
+            .map(|(&(year, day), &puzzle)| (year, day, puzzle))
+    }
+
+    pub fn len(&self) -> usize {
+        self.solutions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.solutions.is_empty()
+    }
+}
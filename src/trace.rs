@@ -0,0 +1,74 @@
+//! Opt-in diagnostics for solvers. Solvers report what they find with
+//! [`trace!`](crate::trace!), which does nothing, not even formatting, unless
+//! the caller installed a sink around the run with [`with_sink`] or
+//! [`capture`]. Sinks are per thread, so parallel tests do not see each
+//! other's traces.
+
+use std::cell::{Cell, RefCell};
+use std::fmt;
+use std::rc::Rc;
+
+type Sink = Box<dyn FnMut(&str)>;
+
+thread_local! {
+    static SINK: RefCell<Option<Sink>> = RefCell::new(None);
+    static ENABLED: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Reports a line of diagnostics to the current sink, if there is one.
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)*) => {
+        if $crate::trace::is_enabled() {
+            $crate::trace::emit(format_args!($($arg)*));
+        }
+    };
+}
+
+pub fn is_enabled() -> bool {
+    ENABLED.with(Cell::get)
+}
+
+pub fn emit(args: fmt::Arguments<'_>) {
+    let line = args.to_string();
+    SINK.with(|sink| {
+        if let Some(sink) = sink.borrow_mut().as_mut() {
+            sink(&line);
+        }
+    });
+}
+
+/// Runs `f` with every traced line passed to `sink`, restoring the previous
+/// sink afterwards.
+pub fn with_sink<R>(
+    sink: impl FnMut(&str) + 'static,
+    f: impl FnOnce() -> R,
+) -> R {
+    let previous = SINK.with(|current| current.replace(Some(Box::new(sink))));
+    let was_enabled = ENABLED.with(|enabled| enabled.replace(true));
+
+    // Restore even if `f` panics, so a failing test leaves no sink behind
+    struct Restore(Option<Sink>, bool);
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            let previous = self.0.take();
+            SINK.with(|current| *current.borrow_mut() = previous);
+            ENABLED.with(|enabled| enabled.set(self.1));
+        }
+    }
+    let _restore = Restore(previous, was_enabled);
+
+    f()
+}
+
+/// Runs `f` and collects every line it traced.
+pub fn capture<R>(f: impl FnOnce() -> R) -> (R, Vec<String>) {
+    let lines = Rc::new(RefCell::new(Vec::new()));
+    let sink = Rc::clone(&lines);
+
+    let result =
+        with_sink(move |line| sink.borrow_mut().push(line.to_string()), f);
+    let lines = lines.take();
+
+    (result, lines)
+}
@@ -0,0 +1,131 @@
+//! Cycle detection for sequences `start, step(start), step(step(start)), ..`
+//! over a finite state space. Every finder returns `(mu, lambda)`: the index
+//! of the first state on the cycle and the cycle length. None of them return
+//! if the sequence never repeats.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Remembers every state seen so far. Needs `O(mu + lambda)` memory but steps
+/// each state once, which pays off when `step` is expensive.
+pub fn find_cycle<S, F>(start: S, step: F) -> (usize, usize)
+where
+    S: Hash + Eq + Clone,
+    F: FnMut(&S) -> S,
+{
+    let (mu, lambda, _) = walk_until_repeat(start, step);
+    (mu, lambda)
+}
+
+/// Floyd's tortoise and hare, in constant memory.
+pub fn find_cycle_floyd<S, F>(start: S, mut step: F) -> (usize, usize)
+where
+    S: Eq + Clone,
+    F: FnMut(&S) -> S,
+{
+    let mut tortoise = step(&start);
+    let mut hare = step(&tortoise);
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        hare = step(&hare);
+    }
+
+    // The hare is now a multiple of lambda ahead, so both meet at mu
+    let mut mu = 0;
+    tortoise = start;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        mu += 1;
+    }
+
+    let mut lambda = 1;
+    hare = step(&tortoise);
+    while tortoise != hare {
+        hare = step(&hare);
+        lambda += 1;
+    }
+
+    (mu, lambda)
+}
+
+/// Brent's algorithm, in constant memory and usually fewer steps than Floyd.
+pub fn find_cycle_brent<S, F>(start: S, mut step: F) -> (usize, usize)
+where
+    S: Eq + Clone,
+    F: FnMut(&S) -> S,
+{
+    let mut power = 1;
+    let mut lambda = 1;
+    let mut tortoise = start.clone();
+    let mut hare = step(&start);
+    while tortoise != hare {
+        if power == lambda {
+            tortoise = hare.clone();
+            power *= 2;
+            lambda = 0;
+        }
+        hare = step(&hare);
+        lambda += 1;
+    }
+
+    // Start the hare lambda steps ahead, so both meet at mu
+    let mut mu = 0;
+    tortoise = start.clone();
+    hare = start;
+    for _ in 0..lambda {
+        hare = step(&hare);
+    }
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        mu += 1;
+    }
+
+    (mu, lambda)
+}
+
+/// The smallest index whose state equals the state at index `n`, given the
+/// `(mu, lambda)` of the sequence.
+pub fn fast_forward(mu: usize, lambda: usize, n: usize) -> usize {
+    if n < mu {
+        n
+    } else {
+        mu + (n - mu) % lambda
+    }
+}
+
+/// The state at index `n`, without stepping more than `mu + lambda` times.
+pub fn nth_state<S, F>(start: S, step: F, n: usize) -> S
+where
+    S: Hash + Eq + Clone,
+    F: FnMut(&S) -> S,
+{
+    let (mu, lambda, mut states) = walk_until_repeat(start, step);
+    states.swap_remove(fast_forward(mu, lambda, n))
+}
+
+/// Steps until a state repeats, returning `(mu, lambda)` and every state up
+/// to the repeat.
+fn walk_until_repeat<S, F>(start: S, mut step: F) -> (usize, usize, Vec<S>)
+where
+    S: Hash + Eq + Clone,
+    F: FnMut(&S) -> S,
+{
+    let mut seen = HashMap::new();
+    let mut states = vec![start];
+
+    loop {
+        let index = states.len() - 1;
+        let state = &states[index];
+        if let Some(&mu) = seen.get(state) {
+            states.pop();
+            return (mu, index - mu, states);
+        }
+
+        seen.insert(state.clone(), index);
+        let next = step(state);
+        states.push(next);
+    }
+}
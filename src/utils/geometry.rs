@@ -0,0 +1,38 @@
+//! Exact integer geometry on lattice polygons, given as their vertices in
+//! order. The last vertex connects back to the first, and collinear or
+//! repeated vertices are allowed.
+
+use crate::utils::math::gcd;
+
+/// Twice the area enclosed by `vertices`, by the shoelace formula. Doubling
+/// keeps the result an integer for every lattice polygon.
+pub fn shoelace_area2(vertices: &[(i64, i64)]) -> i64 {
+    let twice_signed_area: i64 = vertices
+        .iter()
+        .zip(vertices.iter().cycle().skip(1))
+        .map(|(&(x1, y1), &(x2, y2))| x1 * y2 - x2 * y1)
+        .sum();
+
+    twice_signed_area.abs()
+}
+
+/// Number of lattice points on the edges of the polygon.
+pub fn boundary_points(vertices: &[(i64, i64)]) -> i64 {
+    vertices
+        .iter()
+        .zip(vertices.iter().cycle().skip(1))
+        .map(|(&(x1, y1), &(x2, y2))| gcd((x2 - x1).abs(), (y2 - y1).abs()))
+        .sum()
+}
+
+/// Number of lattice points strictly inside the polygon, by Pick's theorem
+/// `A = I + B / 2 - 1`. The polygon must not cross itself.
+pub fn interior_points(vertices: &[(i64, i64)]) -> i64 {
+    let area2 = shoelace_area2(vertices);
+    // Pick's theorem does not hold for polygons without an inside
+    if area2 == 0 {
+        return 0;
+    }
+
+    (area2 - boundary_points(vertices) + 2) / 2
+}
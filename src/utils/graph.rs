@@ -0,0 +1,299 @@
+//! Searches over graphs given as adjacency lists, closures or grids. Every
+//! search returns the distances it found along with enough to rebuild one
+//! shortest path to each node it reached.
+//!
+//! None of the current solvers needs a search, so this module is library-only:
+//! it is covered by its own tests and kept for puzzles that do.
+
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+use std::marker::PhantomData;
+
+use crate::utils::grid::Grid;
+
+pub trait Graph {
+    type Node: Clone + Eq + Hash;
+
+    /// The nodes one edge away from `node`, each with the cost of that edge.
+    fn edges(&self, node: &Self::Node) -> Vec<(Self::Node, u64)>;
+}
+
+/// Every listed neighbour is one step away; unknown nodes have none.
+impl<N: Clone + Eq + Hash> Graph for HashMap<N, Vec<N>> {
+    type Node = N;
+
+    fn edges(&self, node: &N) -> Vec<(N, u64)> {
+        unit_edges(self.get(node))
+    }
+}
+
+/// Every listed neighbour is one step away; unknown nodes have none.
+impl<N: Clone + Ord + Hash> Graph for BTreeMap<N, Vec<N>> {
+    type Node = N;
+
+    fn edges(&self, node: &N) -> Vec<(N, u64)> {
+        unit_edges(self.get(node))
+    }
+}
+
+fn unit_edges<N: Clone>(neighbors: Option<&Vec<N>>) -> Vec<(N, u64)> {
+    neighbors
+        .into_iter()
+        .flatten()
+        .map(|neighbor| (neighbor.clone(), 1))
+        .collect()
+}
+
+/// A graph whose edges are computed on demand, for state spaces too large to
+/// list up front.
+pub struct FnGraph<N, F> {
+    edges: F,
+    node: PhantomData<fn(&N)>,
+}
+
+pub fn from_fn<N, F>(edges: F) -> FnGraph<N, F>
+where
+    N: Clone + Eq + Hash,
+    F: Fn(&N) -> Vec<(N, u64)>,
+{
+    FnGraph {
+        edges,
+        node: PhantomData,
+    }
+}
+
+impl<N, F> Graph for FnGraph<N, F>
+where
+    N: Clone + Eq + Hash,
+    F: Fn(&N) -> Vec<(N, u64)>,
+{
+    type Node = N;
+
+    fn edges(&self, node: &N) -> Vec<(N, u64)> {
+        (self.edges)(node)
+    }
+}
+
+/// The cells of a grid as nodes, with edges between orthogonal neighbours
+/// weighted by `cost(from, to)`, or left out where it returns `None`.
+pub struct GridGraph<'a, T, F> {
+    grid: &'a Grid<T>,
+    cost: F,
+}
+
+impl<'a, T, F> GridGraph<'a, T, F>
+where
+    F: Fn((usize, usize), (usize, usize)) -> Option<u64>,
+{
+    pub fn new(grid: &'a Grid<T>, cost: F) -> Self {
+        GridGraph { grid, cost }
+    }
+}
+
+impl<T, F> Graph for GridGraph<'_, T, F>
+where
+    F: Fn((usize, usize), (usize, usize)) -> Option<u64>,
+{
+    type Node = (usize, usize);
+
+    fn edges(&self, &(x, y): &(usize, usize)) -> Vec<((usize, usize), u64)> {
+        self.grid
+            .neighbors4(x, y)
+            .filter_map(|next| {
+                (self.cost)((x, y), next).map(|cost| (next, cost))
+            })
+            .collect()
+    }
+}
+
+/// Distances from the start of a search to every node it reached.
+#[derive(Debug, Clone)]
+pub struct Paths<N> {
+    start: N,
+    distances: HashMap<N, u64>,
+    parents: HashMap<N, N>,
+}
+
+impl<N: Clone + Eq + Hash> Paths<N> {
+    fn new(start: N) -> Self {
+        Paths {
+            distances: HashMap::from([(start.clone(), 0)]),
+            parents: HashMap::new(),
+            start,
+        }
+    }
+
+    pub fn start(&self) -> &N {
+        &self.start
+    }
+
+    pub fn distance(&self, node: &N) -> Option<u64> {
+        self.distances.get(node).copied()
+    }
+
+    pub fn distances(&self) -> &HashMap<N, u64> {
+        &self.distances
+    }
+
+    /// One shortest path from the start to `node`, both included.
+    pub fn path_to(&self, node: &N) -> Option<Vec<N>> {
+        self.distances.get(node)?;
+
+        let mut path = vec![node.clone()];
+        while let Some(parent) = self.parents.get(path.last()?) {
+            path.push(parent.clone());
+        }
+        path.reverse();
+
+        Some(path)
+    }
+}
+
+/// Breadth-first search, counting every edge as one step whatever its cost.
+pub fn bfs<G: Graph>(graph: &G, start: G::Node) -> Paths<G::Node> {
+    let mut paths = Paths::new(start.clone());
+    let mut queue = VecDeque::from([(start, 0)]);
+
+    while let Some((node, distance)) = queue.pop_front() {
+        for (next, _) in graph.edges(&node) {
+            if !paths.distances.contains_key(&next) {
+                paths.distances.insert(next.clone(), distance + 1);
+                paths.parents.insert(next.clone(), node.clone());
+                queue.push_back((next, distance + 1));
+            }
+        }
+    }
+
+    paths
+}
+
+/// Dijkstra's algorithm, summing edge costs.
+pub fn dijkstra<G: Graph>(graph: &G, start: G::Node) -> Paths<G::Node> {
+    let mut paths = Paths::new(start.clone());
+    let mut queue = Queue::default();
+    queue.push(0, (start, 0));
+
+    while let Some((node, distance)) = queue.pop() {
+        if paths.distances[&node] < distance {
+            continue;
+        }
+
+        for (next, cost) in graph.edges(&node) {
+            let next_distance = distance + cost;
+            if paths.distance(&next).map_or(true, |d| next_distance < d) {
+                paths.distances.insert(next.clone(), next_distance);
+                paths.parents.insert(next.clone(), node.clone());
+                queue.push(next_distance, (next, next_distance));
+            }
+        }
+    }
+
+    paths
+}
+
+/// A* search for the cheapest path from `start` to any node satisfying
+/// `is_goal`, returning its cost and the path. `heuristic` must never
+/// overestimate the remaining cost, or the path found may not be the
+/// cheapest.
+pub fn astar<G, P, H>(
+    graph: &G,
+    start: G::Node,
+    mut is_goal: P,
+    mut heuristic: H,
+) -> Option<(u64, Vec<G::Node>)>
+where
+    G: Graph,
+    P: FnMut(&G::Node) -> bool,
+    H: FnMut(&G::Node) -> u64,
+{
+    let mut paths = Paths::new(start.clone());
+    let mut queue = Queue::default();
+    queue.push(heuristic(&start), (start, 0));
+
+    while let Some((node, distance)) = queue.pop() {
+        if paths.distances[&node] < distance {
+            continue;
+        }
+        if is_goal(&node) {
+            return Some((distance, paths.path_to(&node)?));
+        }
+
+        for (next, cost) in graph.edges(&node) {
+            let next_distance = distance + cost;
+            if paths.distance(&next).map_or(true, |d| next_distance < d) {
+                let estimate = next_distance + heuristic(&next);
+                paths.distances.insert(next.clone(), next_distance);
+                paths.parents.insert(next.clone(), node.clone());
+                queue.push(estimate, (next, next_distance));
+            }
+        }
+    }
+
+    None
+}
+
+/// Groups `nodes` and everything reachable from them into components, in the
+/// order their first node appears, each listed in breadth-first order. Edges
+/// are assumed to go both ways.
+pub fn connected_components<G, I>(graph: &G, nodes: I) -> Vec<Vec<G::Node>>
+where
+    G: Graph,
+    I: IntoIterator<Item = G::Node>,
+{
+    let mut seen = HashSet::new();
+    let mut components = Vec::new();
+
+    for node in nodes {
+        if !seen.insert(node.clone()) {
+            continue;
+        }
+
+        // The component doubles as the queue of nodes left to expand
+        let mut component = vec![node];
+        let mut expanded = 0;
+        while let Some(node) = component.get(expanded) {
+            for (next, _) in graph.edges(node) {
+                if seen.insert(next.clone()) {
+                    component.push(next);
+                }
+            }
+            expanded += 1;
+        }
+
+        components.push(component);
+    }
+
+    components
+}
+
+/// Min-priority queue that does not need its items to be ordered, popping
+/// equal priorities first in, first out.
+struct Queue<T> {
+    heap: BinaryHeap<Reverse<(u64, usize)>>,
+    items: HashMap<usize, T>,
+    pushed: usize,
+}
+
+impl<T> Default for Queue<T> {
+    fn default() -> Self {
+        Queue {
+            heap: BinaryHeap::new(),
+            items: HashMap::new(),
+            pushed: 0,
+        }
+    }
+}
+
+impl<T> Queue<T> {
+    fn push(&mut self, priority: u64, item: T) {
+        self.heap.push(Reverse((priority, self.pushed)));
+        self.items.insert(self.pushed, item);
+        self.pushed += 1;
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        let Reverse((_, index)) = self.heap.pop()?;
+        self.items.remove(&index)
+    }
+}
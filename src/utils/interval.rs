@@ -0,0 +1,252 @@
+use crate::utils::math::Integer;
+use std::cmp::Ordering;
+use std::ops::{Add, Sub};
+
+/// The half-open range `start..end`; empty when `start >= end`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Interval<T> {
+    pub start: T,
+    pub end: T,
+}
+
+impl<T: Copy + Ord> Interval<T> {
+    pub fn new(start: T, end: T) -> Self {
+        Interval { start, end }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start >= self.end
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.start <= value && value < self.end
+    }
+
+    pub fn intersect(&self, other: &Self) -> Option<Self> {
+        let overlap =
+            Interval::new(self.start.max(other.start), self.end.min(other.end));
+        (!overlap.is_empty()).then_some(overlap)
+    }
+
+    /// Splits into the parts below and from `at`, leaving out empty ones.
+    pub fn split_at(&self, at: T) -> (Option<Self>, Option<Self>) {
+        let below = Interval::new(self.start, self.end.min(at));
+        let above = Interval::new(self.start.max(at), self.end);
+
+        (
+            (!below.is_empty()).then_some(below),
+            (!above.is_empty()).then_some(above),
+        )
+    }
+
+    /// The parts of `self` left of and right of `other`, leaving out empty
+    /// ones.
+    pub fn subtract(&self, other: &Self) -> (Option<Self>, Option<Self>) {
+        if other.is_empty() {
+            return ((!self.is_empty()).then_some(*self), None);
+        }
+
+        let (left, _) = self.split_at(other.start);
+        let (_, right) = self.split_at(other.end);
+        (left, right)
+    }
+}
+
+impl<T> Interval<T>
+where
+    T: Copy + Ord + Add<Output = T> + Sub<Output = T>,
+{
+    pub fn from_len(start: T, len: T) -> Self {
+        Interval::new(start, start + len)
+    }
+
+    /// Like [`Interval::from_len`], but `None` when the end does not fit in
+    /// `T`.
+    pub fn checked_from_len(start: T, len: T) -> Option<Self>
+    where
+        T: Integer,
+    {
+        Some(Interval::new(start, start.checked_add(len)?))
+    }
+
+    /// Moves the interval by the offset that takes `from` to `to`. Only
+    /// meaningful when `from <= self.start`, which keeps unsigned types from
+    /// underflowing.
+    pub fn map_offset(&self, from: T, to: T) -> Self {
+        Interval::new(self.start - from + to, self.end - from + to)
+    }
+}
+
+/// A union of intervals, kept sorted with overlapping and touching intervals
+/// merged.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T> Default for IntervalSet<T> {
+    fn default() -> Self {
+        IntervalSet {
+            intervals: Vec::new(),
+        }
+    }
+}
+
+impl<T: Copy + Ord> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn intervals(&self) -> &[Interval<T>] {
+        &self.intervals
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    pub fn min(&self) -> Option<T> {
+        self.intervals.first().map(|interval| interval.start)
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.intervals
+            .binary_search_by(|interval| {
+                if interval.end <= value {
+                    Ordering::Less
+                } else if interval.start > value {
+                    Ordering::Greater
+                } else {
+                    Ordering::Equal
+                }
+            })
+            .is_ok()
+    }
+
+    pub fn insert(&mut self, interval: Interval<T>) {
+        if interval.is_empty() {
+            return;
+        }
+
+        // Everything from `first` to `last` overlaps or touches `interval`
+        let first = self.intervals.partition_point(|i| i.end < interval.start);
+        let last = self.intervals.partition_point(|i| i.start <= interval.end);
+
+        let merged =
+            self.intervals[first..last]
+                .iter()
+                .fold(interval, |merged, i| {
+                    Interval::new(
+                        merged.start.min(i.start),
+                        merged.end.max(i.end),
+                    )
+                });
+        self.intervals.splice(first..last, [merged]);
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut union = self.clone();
+        for &interval in &other.intervals {
+            union.insert(interval);
+        }
+        union
+    }
+
+    pub fn intersect(&self, other: &Self) -> Self {
+        let mut intersection = IntervalSet::new();
+        let (mut i, mut j) = (0, 0);
+
+        while let (Some(a), Some(b)) =
+            (self.intervals.get(i), other.intervals.get(j))
+        {
+            if let Some(overlap) = a.intersect(b) {
+                intersection.intervals.push(overlap);
+            }
+            // Whichever ends first cannot overlap anything further along
+            if a.end < b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+
+        intersection
+    }
+
+    pub fn subtract(&self, other: &Self) -> Self {
+        let mut difference = IntervalSet::new();
+
+        for interval in &self.intervals {
+            let mut rest = Some(*interval);
+
+            for cut in &other.intervals {
+                let Some(current) = rest else { break };
+                if cut.start >= current.end {
+                    break;
+                }
+
+                let (left, right) = current.subtract(cut);
+                if let Some(left) = left {
+                    difference.intervals.push(left);
+                }
+                rest = right;
+            }
+
+            difference.intervals.extend(rest);
+        }
+
+        difference
+    }
+
+    /// Splits into the values below and from `at`.
+    pub fn split_at(&self, at: T) -> (Self, Self) {
+        let mut below = IntervalSet::new();
+        let mut above = IntervalSet::new();
+
+        for interval in &self.intervals {
+            let (left, right) = interval.split_at(at);
+            below.intervals.extend(left);
+            above.intervals.extend(right);
+        }
+
+        (below, above)
+    }
+}
+
+impl<T> IntervalSet<T>
+where
+    T: Copy + Ord + Add<Output = T> + Sub<Output = T>,
+{
+    /// Maps every value in a `(source, destination start)` range by the
+    /// offset from the source start to the destination start, keeping values
+    /// outside of all sources as they are. A value covered by several sources
+    /// is mapped by the first.
+    pub fn map_offsets<I>(&self, mappings: I) -> Self
+    where
+        I: IntoIterator<Item = (Interval<T>, T)>,
+    {
+        let mut unmapped = self.clone();
+        let mut mapped = IntervalSet::new();
+
+        for (source, destination) in mappings {
+            let sources = IntervalSet::from_iter([source]);
+
+            for interval in unmapped.intersect(&sources).intervals {
+                mapped.insert(interval.map_offset(source.start, destination));
+            }
+            unmapped = unmapped.subtract(&sources);
+        }
+
+        mapped.union(&unmapped)
+    }
+}
+
+impl<T: Copy + Ord> FromIterator<Interval<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Interval<T>>>(iter: I) -> Self {
+        let mut set = IntervalSet::new();
+        for interval in iter {
+            set.insert(interval);
+        }
+        set
+    }
+}
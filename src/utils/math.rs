@@ -0,0 +1,303 @@
+//! Number theory over every primitive integer type. Modular arithmetic never
+//! computes a value larger than the modulus, so it cannot overflow as long as
+//! the modulus itself fits.
+
+use std::fmt::Debug;
+use std::ops::{Add, Div, Mul, Rem, Sub};
+
+pub trait Integer:
+    Copy
+    + Ord
+    + Debug
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+}
+
+/// Integer types with negative values, as needed for Bézout coefficients.
+pub trait Signed: Integer {}
+
+macro_rules! impl_integer {
+    ($($t:ty),*) => {
+        $(
+            impl Integer for $t {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                fn checked_add(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_add(self, rhs)
+                }
+
+                fn checked_sub(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_sub(self, rhs)
+                }
+
+                fn checked_mul(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_mul(self, rhs)
+                }
+            }
+        )*
+    };
+}
+
+impl_integer!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+impl Signed for i8 {}
+impl Signed for i16 {}
+impl Signed for i32 {}
+impl Signed for i64 {}
+impl Signed for i128 {}
+impl Signed for isize {}
+
+fn abs<T: Integer>(a: T) -> T {
+    if a < T::ZERO {
+        T::ZERO - a
+    } else {
+        a
+    }
+}
+
+/// Greatest common divisor, never negative; `gcd(0, 0) = 0`.
+pub fn gcd<T: Integer>(mut a: T, mut b: T) -> T {
+    while b != T::ZERO {
+        (a, b) = (b, a % b);
+    }
+    abs(a)
+}
+
+/// Least common multiple, never negative; 0 if either argument is 0.
+///
+/// # Panics
+///
+/// Panics if the result does not fit in `T`; see [`checked_lcm`].
+pub fn lcm<T: Integer>(a: T, b: T) -> T {
+    checked_lcm(a, b).expect("lcm overflowed")
+}
+
+/// Least common multiple, or `None` if it does not fit in `T`.
+pub fn checked_lcm<T: Integer>(a: T, b: T) -> Option<T> {
+    if a == T::ZERO || b == T::ZERO {
+        return Some(T::ZERO);
+    }
+
+    // Dividing first keeps the intermediate no larger than the result
+    abs(a / gcd(a, b)).checked_mul(abs(b))
+}
+
+/// The largest `r` with `r * r <= n`.
+///
+/// # Panics
+///
+/// Panics if `n` is negative.
+pub fn isqrt<T: Integer>(n: T) -> T {
+    assert!(n >= T::ZERO, "square root of a negative number");
+
+    let two = T::ONE + T::ONE;
+    if n < two {
+        return n;
+    }
+
+    // Newton's method from above: `n / 2 + 1` is never below the root, and
+    // every step stays at or above it until the estimate stops decreasing.
+    // No intermediate exceeds `n / 2 + 2`.
+    let mut root = n / two + T::ONE;
+    loop {
+        let next = (root + n / root) / two;
+        if next >= root {
+            return root;
+        }
+        root = next;
+    }
+}
+
+/// `(g, x, y)` with `g = gcd(a, b) = a * x + b * y`.
+pub fn extended_gcd<T: Signed>(a: T, b: T) -> (T, T, T) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (T::ONE, T::ZERO);
+    let (mut old_y, mut y) = (T::ZERO, T::ONE);
+
+    while r != T::ZERO {
+        let quotient = old_r / r;
+        (old_r, r) = (r, old_r - quotient * r);
+        (old_x, x) = (x, old_x - quotient * x);
+        (old_y, y) = (y, old_y - quotient * y);
+    }
+
+    if old_r < T::ZERO {
+        (T::ZERO - old_r, T::ZERO - old_x, T::ZERO - old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// `a` modulo `modulus`, in `0..modulus`.
+///
+/// # Panics
+///
+/// Panics if `modulus` is not positive.
+pub fn rem_euclid<T: Integer>(a: T, modulus: T) -> T {
+    assert!(modulus > T::ZERO, "modulus must be positive");
+
+    let remainder = a % modulus;
+    if remainder < T::ZERO {
+        remainder + modulus
+    } else {
+        remainder
+    }
+}
+
+/// `(a + b) mod modulus` for `a` and `b` already in `0..modulus`.
+fn add_mod<T: Integer>(a: T, b: T, modulus: T) -> T {
+    if a >= modulus - b {
+        a - (modulus - b)
+    } else {
+        a + b
+    }
+}
+
+/// `(a * b) mod modulus`, by doubling and adding so that nothing overflows.
+pub fn mul_mod<T: Integer>(a: T, b: T, modulus: T) -> T {
+    let two = T::ONE + T::ONE;
+    let (mut a, mut b) = (rem_euclid(a, modulus), rem_euclid(b, modulus));
+    let mut product = T::ZERO;
+
+    while b > T::ZERO {
+        if b % two == T::ONE {
+            product = add_mod(product, a, modulus);
+        }
+        a = add_mod(a, a, modulus);
+        b = b / two;
+    }
+
+    product
+}
+
+/// `base ^ exponent mod modulus` by repeated squaring.
+///
+/// # Panics
+///
+/// Panics if `exponent` is negative or `modulus` is not positive.
+pub fn mod_pow<T: Integer>(base: T, mut exponent: T, modulus: T) -> T {
+    assert!(exponent >= T::ZERO, "exponent must not be negative");
+
+    let two = T::ONE + T::ONE;
+    let mut base = rem_euclid(base, modulus);
+    let mut result = rem_euclid(T::ONE, modulus);
+
+    while exponent > T::ZERO {
+        if exponent % two == T::ONE {
+            result = mul_mod(result, base, modulus);
+        }
+        base = mul_mod(base, base, modulus);
+        exponent = exponent / two;
+    }
+
+    result
+}
+
+/// The `x` in `0..modulus` with `a * x = 1 (mod modulus)`, if `a` and
+/// `modulus` are coprime.
+pub fn mod_inverse<T: Integer>(a: T, modulus: T) -> Option<T> {
+    // Extended Euclid, keeping the coefficient of `a` modulo `modulus` so
+    // that unsigned types work too
+    let (mut old_r, mut r) = (rem_euclid(a, modulus), modulus);
+    let (mut old_x, mut x) = (rem_euclid(T::ONE, modulus), T::ZERO);
+
+    while r != T::ZERO {
+        let quotient = old_r / r;
+        let product = mul_mod(quotient, x, modulus);
+        (old_r, r) = (r, old_r - quotient * r);
+        (old_x, x) = (x, add_mod(old_x, modulus - product, modulus));
+    }
+
+    (old_r == T::ONE || modulus == T::ONE).then_some(old_x)
+}
+
+/// Solves the system `x = residue (mod modulus)` for every pair, where the
+/// moduli need not be coprime. Returns `x` in `0..m` and `m`, the lcm of the
+/// moduli, or `None` if the congruences contradict each other or `m` does not
+/// fit in `T`. An empty system is solved by `(0, 1)`.
+///
+/// # Panics
+///
+/// Panics if a modulus is not positive.
+pub fn crt<T, I>(congruences: I) -> Option<(T, T)>
+where
+    T: Integer,
+    I: IntoIterator<Item = (T, T)>,
+{
+    congruences.into_iter().try_fold(
+        (T::ZERO, T::ONE),
+        |(a, m), (residue, n)| {
+            let b = rem_euclid(residue, n);
+            let g = gcd(m, n);
+
+            // x = a + m * k, so m * k = b - a (mod n) must be solvable
+            let difference = add_mod(b, n - rem_euclid(a, n), n);
+            if difference % g != T::ZERO {
+                return None;
+            }
+
+            let step = n / g;
+            let k = mul_mod(difference / g, mod_inverse(m / g, step)?, step);
+            let lcm = (m / g).checked_mul(n)?;
+
+            // m * k < lcm since k < n / g, and a < m, so this fits
+            Some((a + m * k, lcm))
+        },
+    )
+}
+
+/// The first entry of every row of the difference table of `values`, up to
+/// and excluding the first row that is all zeros. These are the coefficients
+/// of the lowest-degree polynomial through `values` in Newton's forward form.
+/// Returns `None` if a difference overflows.
+pub fn forward_differences<T: Integer>(values: &[T]) -> Option<Vec<T>> {
+    let mut row = values.to_vec();
+    let mut leading = Vec::new();
+
+    while row.iter().any(|&value| value != T::ZERO) {
+        leading.push(row[0]);
+        row = row
+            .windows(2)
+            .map(|pair| pair[1].checked_sub(pair[0]))
+            .collect::<Option<_>>()?;
+    }
+
+    Some(leading)
+}
+
+/// The value at index `x` of the lowest-degree polynomial taking `values` at
+/// indices `0, 1, ..`, so `values.len()` is the next value and `-1` the one
+/// before the first. Exact, and `None` for no values or on overflow.
+pub fn extrapolate<T: Signed>(values: &[T], x: T) -> Option<T> {
+    if values.is_empty() {
+        return None;
+    }
+
+    // Sum of `differences[k] * binomial(x, k)`, where the binomial stays an
+    // integer for every `x`, negative ones too
+    let mut value = T::ZERO;
+    let mut binomial = T::ONE;
+    let mut k = T::ZERO;
+
+    for (i, difference) in forward_differences(values)?.into_iter().enumerate()
+    {
+        if i > 0 {
+            binomial = binomial.checked_mul(x.checked_sub(k)?)?;
+            k = k + T::ONE;
+            binomial = binomial / k;
+        }
+        value = value.checked_add(difference.checked_mul(binomial)?)?;
+    }
+
+    Some(value)
+}
@@ -1,3 +1,10 @@
+pub mod cycle;
+pub mod geometry;
+pub mod graph;
+pub mod grid;
+pub mod interval;
+pub mod math;
+
 #[derive(Debug)]
 struct Kmh {
     value: u32,
@@ -63,28 +70,34 @@ pub fn associated_types() {
     let speed = Kmh { value: 90 };
     let distance = speed.distance_in_three_hours();
 
-    println!("At {:?}, you will travel {:?} in 3 hours", speed, distance);
+    println!(
+        "At {:?}, you will travel {} km in 3 hours",
+        speed, distance.value
+    );
 
-    let speed_Mph = Mph { value: 90 };
-    let distance_Miles = speed_Mph.distance_in_three_hours();
+    let speed_mph = Mph { value: 90 };
+    let distance_miles = speed_mph.distance_in_three_hours();
     println!(
-        "At {:?}, you will travel {:?} in 3 hours",
-        speed_Mph, distance_Miles
+        "At {:?}, you will travel {} miles in 3 hours",
+        speed_mph, distance_miles.value
     );
 }
 
+/// # Safety
+///
+/// Does nothing unsafe; it only exists to demonstrate calling an `unsafe fn`.
 pub unsafe fn dangerous() {
     println!("a dangerous function call...");
 }
 
 pub fn raw_pointers() {
     let num: i32 = 10;
-    let num_ptr: *const i32 = &num; // &num as *const i32
+    let _num_ptr: *const i32 = &num; // &num as *const i32
     let mut num_2: i32 = 20;
-    let num_ptr_2: *mut i32 = &mut num_2; // &mut num_2 as *mut i32
+    let _num_ptr_2: *mut i32 = &mut num_2; // &mut num_2 as *mut i32
 
     let numb: Box<i32> = Box::new(30);
-    let numb_ptr: *const i32 = &*numb;
+    let _numb_ptr: *const i32 = &*numb;
     let mut numb_2: Box<i32> = Box::new(50);
     let numb_ptr_2: *mut i32 = &mut *numb_2;
 
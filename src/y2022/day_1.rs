@@ -1,43 +1,58 @@
+use crate::error::{parse_token, AocResult};
+use crate::solution::{Answer, Solution};
+use crate::trace;
 use std::fs::File;
 use std::io::{self, BufRead};
 
-pub fn process_file(file_path: &str) -> (u32, u32) {
+pub fn process_file(file_path: &str) -> AocResult<(u32, u32)> {
+    let file = File::open(file_path)?;
+    process_reader(io::BufReader::new(file))
+}
+
+pub fn process_str(input: &str) -> AocResult<(u32, u32)> {
+    process_reader(input.as_bytes())
+}
+
+pub fn process_reader(reader: impl BufRead) -> AocResult<(u32, u32)> {
+    let sums = read_elf_calories(reader)?;
+
+    Ok((max_calories(&sums), top_three_calories(&sums)))
+}
+
+/// The total calories carried by every elf, in input order.
+pub fn read_elf_calories(reader: impl BufRead) -> AocResult<Vec<u32>> {
     let mut batch: Vec<u32> = Vec::new();
     let mut sums: Vec<u32> = Vec::new();
 
-    if let Ok(file) = File::open(file_path) {
-        let reader = io::BufReader::new(file);
-
-        for line in reader.lines() {
-            if let Ok(line_content) = line {
-                if !line_content.is_empty() {
-                    let value: u32 = line_content
-                        .split_whitespace()
-                        .filter(|s| !s.is_empty())
-                        .filter_map(|s| s.parse().ok())
-                        .next()
-                        .unwrap_or_default();
-
-                    batch.push(value);
-                    println!("{:?}", value);
-                } else {
-                    sums.push(batch.iter().fold(0, |acc, &x| acc + x));
-                    batch = Vec::new();
-                }
-            }
-        }
+    for (index, line) in reader.lines().enumerate() {
+        let line_content = line?;
+        let calories = line_content.trim();
+
+        if !calories.is_empty() {
+            let value: u32 = parse_token(index + 1, &line_content, calories)?;
 
-        if batch.len() > 0 {
-            sums.push(batch.iter().fold(0, |acc, &x| acc + x));
+            batch.push(value);
+        } else {
+            sums.push(batch.iter().sum::<u32>());
+            trace!("elf {}: {} calories", sums.len(), sums[sums.len() - 1]);
+            batch = Vec::new();
         }
-    } else {
-        eprintln!("Error opening file: {}", file_path);
     }
 
-    let max = sums
-        .iter()
-        .fold(u32::MIN, |current_max, &x| current_max.max(x));
+    if !batch.is_empty() {
+        sums.push(batch.iter().sum::<u32>());
+        trace!("elf {}: {} calories", sums.len(), sums[sums.len() - 1]);
+    }
+
+    Ok(sums)
+}
 
+pub fn max_calories(sums: &[u32]) -> u32 {
+    sums.iter()
+        .fold(u32::MIN, |current_max, &x| current_max.max(x))
+}
+
+pub fn top_three_calories(sums: &[u32]) -> u32 {
     let top3: Vec<u32> = sums.iter().fold(vec![], |mut acc, &x| {
         if acc.len() < 3 {
             acc.push(x);
@@ -50,5 +65,23 @@ pub fn process_file(file_path: &str) -> (u32, u32) {
         acc
     });
 
-    (max, top3.iter().fold(0, |acc, &x| acc + x))
+    top3.iter().sum::<u32>()
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Input = Vec<u32>;
+
+    fn parse(&self, input: &str) -> AocResult<Self::Input> {
+        read_elf_calories(input.as_bytes())
+    }
+
+    fn part1(&self, sums: &Self::Input) -> AocResult<Answer> {
+        Ok(max_calories(sums).into())
+    }
+
+    fn part2(&self, sums: &Self::Input) -> AocResult<Answer> {
+        Ok(top_three_calories(sums).into())
+    }
 }
@@ -1,3 +1,5 @@
+use crate::error::{column_of, AocError, AocResult};
+use crate::solution::{Answer, Solution};
 use std::fs::File;
 use std::io::{self, BufRead};
 
@@ -74,58 +76,107 @@ impl HandShape {
     }
 }
 
-impl Into<HandShape> for char {
-    fn into(self) -> HandShape {
-        HandShape::from_char(self).unwrap_or(HandShape::None)
+impl From<char> for HandShape {
+    fn from(val: char) -> Self {
+        HandShape::from_char(val).unwrap_or(HandShape::None)
     }
 }
 
-pub fn process_file(file_path: &str) -> (i32, i32) {
-    if let Ok(file) = File::open(file_path) {
-        let reader = io::BufReader::new(file);
-
-        let mut total_score = 0;
-        let mut total_converted_score = 0;
-
-        for line in reader.lines() {
-            let line = line.unwrap();
-            let mut iter = line.split_whitespace();
-
-            while let (Some(opponent_choice), Some(player_choice)) =
-                (iter.next(), iter.next())
-            {
-                // Convert each substring to &char
-                if let (Some(opponent_char), Some(player_char)) = (
-                    opponent_choice.chars().next(),
-                    player_choice.chars().next(),
-                ) {
-                    let opponent_shape = HandShape::from_char(opponent_char);
-                    let player_shape = HandShape::from_char(player_char);
-
-                    if let (Some(opponent_shape), Some(player_shape)) =
-                        (opponent_shape, player_shape)
-                    {
-                        // println!(
-                        //     "opponent_choice: {:?}, player_choice: {:?}",
-                        //     opponent_shape, player_shape
-                        // );
-                        let result = opponent_shape.compare(&player_shape);
-                        let score = result + player_shape.get_score();
-                        total_score += score;
-
-                        let converted_result =
-                            player_shape.convert_score(&opponent_shape);
-                        total_converted_score += converted_result;
-                    } else {
-                        println!("Invalid choices.");
-                    }
-                }
-            }
+pub fn process_file(file_path: &str) -> AocResult<(i32, i32)> {
+    let file = File::open(file_path)?;
+    process_reader(io::BufReader::new(file))
+}
+
+pub fn process_str(input: &str) -> AocResult<(i32, i32)> {
+    process_reader(input.as_bytes())
+}
+
+pub fn process_reader(reader: impl BufRead) -> AocResult<(i32, i32)> {
+    let rounds = read_rounds(reader)?;
+
+    Ok((total_score(&rounds), total_converted_score(&rounds)))
+}
+
+/// The opponent's and the player's shape of every round.
+pub type Round = (HandShape, HandShape);
+
+pub fn read_rounds(reader: impl BufRead) -> AocResult<Vec<Round>> {
+    let mut rounds = Vec::new();
+
+    for (index, line) in reader.lines().enumerate() {
+        let line = line?;
+        let mut iter = line.split_whitespace();
+
+        while let Some(opponent_choice) = iter.next() {
+            let player_choice = iter.next().ok_or_else(|| {
+                AocError::parse(
+                    index + 1,
+                    line.len() + 1,
+                    "expected the player's choice",
+                )
+            })?;
+
+            let opponent_shape =
+                parse_shape(index + 1, &line, opponent_choice)?;
+            let player_shape = parse_shape(index + 1, &line, player_choice)?;
+            rounds.push((opponent_shape, player_shape));
         }
+    }
+
+    Ok(rounds)
+}
+
+/// The score when the second column is the shape to play.
+pub fn total_score(rounds: &[Round]) -> i32 {
+    rounds
+        .iter()
+        .map(|(opponent_shape, player_shape)| {
+            opponent_shape.compare(player_shape) + player_shape.get_score()
+        })
+        .sum()
+}
+
+/// The score when the second column is the outcome to aim for.
+pub fn total_converted_score(rounds: &[Round]) -> i32 {
+    rounds
+        .iter()
+        .map(|(opponent_shape, player_shape)| {
+            player_shape.convert_score(opponent_shape)
+        })
+        .sum()
+}
+
+fn parse_shape(
+    line_number: usize,
+    line: &str,
+    choice: &str,
+) -> AocResult<HandShape> {
+    let mut chars = choice.chars();
+
+    match (chars.next().and_then(HandShape::from_char), chars.next()) {
+        (Some(shape), None) => Ok(shape),
+        _ => Err(AocError::parse(
+            line_number,
+            column_of(line, choice),
+            format!("unknown hand shape `{}`", choice),
+        )),
+    }
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Input = Vec<Round>;
+
+    fn parse(&self, input: &str) -> AocResult<Self::Input> {
+        read_rounds(input.as_bytes())
+    }
+
+    fn part1(&self, rounds: &Self::Input) -> AocResult<Answer> {
+        Ok(total_score(rounds).into())
+    }
 
-        (total_score, total_converted_score)
-    } else {
-        eprintln!("Error opening file: {}", file_path);
-        (0, 0)
+    fn part2(&self, rounds: &Self::Input) -> AocResult<Answer> {
+        Ok(total_converted_score(rounds).into())
     }
 }
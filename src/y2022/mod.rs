@@ -1,2 +1,9 @@
+use crate::solution::Registry;
+
 pub mod day_1;
 pub mod day_2;
+
+pub fn register(registry: &mut Registry) {
+    registry.register(2022, 1, &day_1::Day1);
+    registry.register(2022, 2, &day_2::Day2);
+}
@@ -1,3 +1,6 @@
+use crate::error::{AocError, AocResult};
+use crate::solution::{Answer, Solution};
+use crate::trace;
 use std::collections::{BTreeMap, HashMap};
 use std::fs::File;
 use std::io::{self, BufRead};
@@ -13,29 +16,44 @@ pub fn extract_first_and_last_digits(input: &str) -> Option<u32> {
     None
 }
 
-pub fn process_file(file_path: &str) -> io::Result<u32> {
+pub fn process_file(file_path: &str) -> AocResult<u32> {
     let file = File::open(file_path)?;
-    let reader = io::BufReader::new(file);
+    process_reader(io::BufReader::new(file))
+}
 
-    let mut total: u32 = 0;
+pub fn process_str(input: &str) -> AocResult<u32> {
+    process_reader(input.as_bytes())
+}
 
-    for line in reader.lines() {
-        if let Ok(line_content) = line {
-            let modified_input =
-                find_and_replace_substrings(line_content.as_str());
+pub fn process_reader(reader: impl BufRead) -> AocResult<u32> {
+    let lines = reader.lines().collect::<io::Result<Vec<String>>>()?;
 
-            if let Some(combined_digits) =
-                extract_first_and_last_digits(&modified_input)
-            {
-                // println!(
-                //     "Input: {:?}, Combined Digits: {}",
-                //     line_content, combined_digits
-                // );
-                total += combined_digits;
-            } else {
-                println!("Input: {:?}, No digits found", line_content);
-            }
+    sum_calibration_values(&lines, true)
+}
+
+/// Sums up the calibration value of every line, skipping blank ones. Every
+/// other line must hold at least one digit.
+pub fn sum_calibration_values(
+    lines: &[String],
+    spelled_out: bool,
+) -> AocResult<u32> {
+    let mut total: u32 = 0;
+
+    for (index, line_content) in lines.iter().enumerate() {
+        if line_content.trim().is_empty() {
+            continue;
         }
+
+        let modified_input = if spelled_out {
+            find_and_replace_substrings(line_content.as_str())
+        } else {
+            line_content.clone()
+        };
+
+        let combined_digits = extract_first_and_last_digits(&modified_input)
+            .ok_or_else(|| AocError::parse(index + 1, 1, "no digits found"))?;
+        trace!("{:?}: {}", line_content, combined_digits);
+        total += combined_digits;
     }
 
     Ok(total)
@@ -87,7 +105,7 @@ pub fn find_all_substrings_in_array(
 ) -> BTreeMap<usize, String> {
     let mut map = BTreeMap::new();
 
-    for (_index, substring) in substrings.iter().enumerate() {
+    for substring in substrings.iter() {
         for found in input.match_indices(substring) {
             let absolute_index = found.0;
             map.insert(absolute_index, substring.to_string());
@@ -110,13 +128,11 @@ pub fn find_and_replace_substrings(input: &str) -> String {
     let word_to_number_map = create_word_to_number_map();
     let mut keys: Vec<&str> =
         word_to_number_map.keys().map(|s| s.as_str()).collect();
-    keys.sort_by(|a, b| b.len().cmp(&a.len()));
+    keys.sort_by_key(|key| std::cmp::Reverse(key.len()));
 
     let map = find_all_substrings_in_array(input, &keys);
 
     let mut modified_input = String::from(input);
-    let mut last_pos: &usize = &0;
-    let mut last_value = "";
     let mut index_offset = 0;
 
     // Iterate over the BTreeMap
@@ -130,9 +146,7 @@ pub fn find_and_replace_substrings(input: &str) -> String {
                 (pos - index_offset)..(value.len() + pos - index_offset),
                 &number.to_string(),
             );
-            index_offset += &value.len() - &number.to_string().len();
-            last_pos = pos;
-            last_value = value;
+            index_offset += value.len() - number.to_string().len();
             break;
         }
     }
@@ -172,3 +186,21 @@ pub fn find_and_replace_substrings(input: &str) -> String {
 
     modified_input
 }
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Input = Vec<String>;
+
+    fn parse(&self, input: &str) -> AocResult<Self::Input> {
+        Ok(input.lines().map(String::from).collect())
+    }
+
+    fn part1(&self, lines: &Self::Input) -> AocResult<Answer> {
+        Ok(sum_calibration_values(lines, false)?.into())
+    }
+
+    fn part2(&self, lines: &Self::Input) -> AocResult<Answer> {
+        Ok(sum_calibration_values(lines, true)?.into())
+    }
+}
@@ -1,3 +1,8 @@
+use crate::error::{AocError, AocResult};
+use crate::solution::{Answer, Solution};
+use crate::utils::geometry::interior_points;
+use crate::utils::grid::Grid;
+use std::fmt;
 use std::fs::File;
 use std::io::{self, BufRead};
 
@@ -10,11 +15,10 @@ pub enum Pipe {
     SouthWest(Direction, Direction),  // 7
     SouthEast(Direction, Direction),  // F
     Ground,                           // .
-    StartPosition,                    // S
 }
 
 impl Pipe {
-    fn from_char(c: char) -> Option<Pipe> {
+    pub fn from_char(c: char) -> Option<Pipe> {
         match c {
             '|' => Some(Pipe::Vertical(Direction::Top, Direction::Down)),
             '-' => Some(Pipe::Horizontal(Direction::Left, Direction::Right)),
@@ -23,32 +27,45 @@ impl Pipe {
             '7' => Some(Pipe::SouthWest(Direction::Down, Direction::Left)),
             'F' => Some(Pipe::SouthEast(Direction::Down, Direction::Right)),
             '.' => Some(Pipe::Ground),
-            'S' => Some(Pipe::StartPosition),
             _ => None,
         }
     }
 
-    fn get_directions(&self) -> (Direction, Direction) {
+    /// The pipe joining `a` and `b`, in either order.
+    pub fn from_directions(a: Direction, b: Direction) -> Option<Pipe> {
+        "|-LJ7F".chars().filter_map(Pipe::from_char).find(|pipe| {
+            pipe.get_directions()
+                .is_some_and(|(x, y)| (x, y) == (a, b) || (x, y) == (b, a))
+        })
+    }
+
+    pub fn to_char(&self) -> char {
+        match self {
+            Pipe::Vertical(_, _) => '|',
+            Pipe::Horizontal(_, _) => '-',
+            Pipe::NorthEast(_, _) => 'L',
+            Pipe::NorthWest(_, _) => 'J',
+            Pipe::SouthWest(_, _) => '7',
+            Pipe::SouthEast(_, _) => 'F',
+            Pipe::Ground => '.',
+        }
+    }
+
+    pub fn connects(&self, direction: Direction) -> bool {
+        self.get_directions()
+            .is_some_and(|(a, b)| a == direction || b == direction)
+    }
+
+    /// The two sides the pipe connects, or `None` for ground.
+    fn get_directions(&self) -> Option<(Direction, Direction)> {
         match *self {
-            Pipe::Vertical(Direction::Top, Direction::Down) => {
-                (Direction::Top, Direction::Down)
-            }
-            Pipe::Horizontal(Direction::Left, Direction::Right) => {
-                (Direction::Left, Direction::Right)
-            }
-            Pipe::NorthEast(Direction::Top, Direction::Right) => {
-                (Direction::Top, Direction::Right)
-            }
-            Pipe::NorthWest(Direction::Top, Direction::Left) => {
-                (Direction::Top, Direction::Left)
-            }
-            Pipe::SouthWest(Direction::Down, Direction::Left) => {
-                (Direction::Down, Direction::Left)
-            }
-            Pipe::SouthEast(Direction::Down, Direction::Right) => {
-                (Direction::Down, Direction::Right)
-            }
-            _ => (Direction::None, Direction::None),
+            Pipe::Vertical(a, b)
+            | Pipe::Horizontal(a, b)
+            | Pipe::NorthEast(a, b)
+            | Pipe::NorthWest(a, b)
+            | Pipe::SouthWest(a, b)
+            | Pipe::SouthEast(a, b) => Some((a, b)),
+            Pipe::Ground => None,
         }
     }
 }
@@ -59,341 +76,215 @@ pub enum Direction {
     Down,
     Left,
     Right,
-    None,
 }
 
 impl Direction {
-    fn iter(&self) -> impl Iterator<Item = &Direction> {
-        std::iter::once(self)
-    }
-}
-
-impl Into<Pipe> for char {
-    fn into(self) -> Pipe {
-        Pipe::from_char(self).unwrap_or(Pipe::Ground)
-    }
-}
-
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub struct Point {
-    pub x: usize,
-    pub y: usize,
-    pub pipe: Pipe,
-}
-
-impl Point {
-    pub fn new(x: usize, y: usize, pipe: Pipe) -> Self {
-        Point { x, y, pipe }
-    }
-
-    pub fn step(&mut self, dx: i32, dy: i32, pipe: &Pipe) {
-        self.x = (self.x as i32 + dx) as usize;
-        self.y = (self.y as i32 + dy) as usize;
-        self.pipe = pipe.clone();
-    }
-
-    pub fn is_start_point_connected_to(
-        &self,
-        other: &Point,
-        direction: &Direction,
-    ) -> bool {
-        match (other.pipe, direction) {
-            (Pipe::Vertical(_, _), Direction::Top)
-            | (Pipe::SouthWest(_, _), Direction::Top)
-            | (Pipe::SouthEast(_, _), Direction::Top)
-            | (Pipe::Vertical(_, _), Direction::Down)
-            | (Pipe::NorthWest(_, _), Direction::Down)
-            | (Pipe::NorthEast(_, _), Direction::Down)
-            | (Pipe::Horizontal(_, _), Direction::Left)
-            | (Pipe::SouthEast(_, _), Direction::Left)
-            | (Pipe::NorthEast(_, _), Direction::Left)
-            | (Pipe::Horizontal(_, _), Direction::Right)
-            | (Pipe::SouthWest(_, _), Direction::Right)
-            | (Pipe::NorthWest(_, _), Direction::Right) => true,
-
-            _ => false,
-        }
-    }
-}
-
-fn get_start_directions(
-    matrix: &Vec<Vec<char>>,
-    current_position: &Point,
-) -> Vec<Direction> {
-    let directions: Vec<Direction> = vec![
+    const ALL: [Direction; 4] = [
         Direction::Top,
         Direction::Right,
         Direction::Down,
         Direction::Left,
     ];
 
-    let mut directions_to_start: Vec<Direction> = Vec::new();
-
-    for direction in directions.iter() {
-        let mut is_connected = false;
-        match *direction {
-            Direction::Top => {
-                if current_position.y > 0 {
-                    // top exists
-                    let other_point = Point::new(
-                        current_position.x,
-                        current_position.y - 1,
-                        matrix[current_position.y - 1][current_position.x]
-                            .into(),
-                    );
-                    is_connected = current_position
-                        .is_start_point_connected_to(&other_point, direction);
-                    if is_connected {
-                        directions_to_start.push(*direction);
-                    }
-                }
-            }
-            Direction::Down => {
-                if current_position.y < matrix[0].len() - 1 {
-                    // down exists
-                    let other_point = Point::new(
-                        current_position.x,
-                        current_position.y + 1,
-                        matrix[current_position.y + 1][current_position.x]
-                            .into(),
-                    );
-                    is_connected = current_position
-                        .is_start_point_connected_to(&other_point, direction);
-                    if is_connected {
-                        directions_to_start.push(*direction);
-                    }
-                }
-            }
-            Direction::Left => {
-                if current_position.x > 0 {
-                    // left exists
-                    let other_point = Point::new(
-                        current_position.x - 1,
-                        current_position.y,
-                        matrix[current_position.y][current_position.x - 1]
-                            .into(),
-                    );
-                    is_connected = current_position
-                        .is_start_point_connected_to(&other_point, direction);
-                    if is_connected {
-                        directions_to_start.push(*direction);
-                    }
-                }
-            }
-            Direction::Right => {
-                if current_position.x < matrix.len() - 1 {
-                    // right exists
-                    let other_point = Point::new(
-                        current_position.x + 1,
-                        current_position.y,
-                        matrix[current_position.y][current_position.x + 1]
-                            .into(),
-                    );
-                    is_connected = current_position
-                        .is_start_point_connected_to(&other_point, direction);
-                    if is_connected {
-                        directions_to_start.push(*direction);
-                    }
-                }
-            }
-            _ => (),
+    fn delta(&self) -> (isize, isize) {
+        match self {
+            Direction::Top => (0, -1),
+            Direction::Right => (1, 0),
+            Direction::Down => (0, 1),
+            Direction::Left => (-1, 0),
         }
     }
 
-    directions_to_start
-}
-
-fn traverse(
-    current_position: &mut Point,
-    matrix: &Vec<Vec<char>>,
-    direction: &Direction,
-) -> (f64, f64) {
-    let (mut dx, mut dy) = (0, 0);
-    match direction {
-        Direction::Top => {
-            dy = -1;
-        }
-        Direction::Right => {
-            dx = 1;
-        }
-        Direction::Down => {
-            dy = 1;
+    fn opposite(&self) -> Direction {
+        match self {
+            Direction::Top => Direction::Down,
+            Direction::Right => Direction::Left,
+            Direction::Down => Direction::Top,
+            Direction::Left => Direction::Right,
         }
-        Direction::Left => {
-            dx = -1;
-        }
-        _ => (),
     }
+}
 
-    let new_x = (current_position.x as i32 + dx) as usize;
-    let new_y = (current_position.y as i32 + dy) as usize;
-
-    let next_pipe = matrix[new_y][new_x].into();
-    current_position.step(dx, dy, &next_pipe);
-
-    (new_x as f64, new_y as f64)
+impl fmt::Display for Pipe {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_char())
+    }
 }
 
-fn peek_next_point(
-    current_position: &Point,
-    last_position: &Point,
-    direction: &Direction,
-) -> bool {
-    let (mut dx, mut dy) = (0, 0);
-    match direction {
-        Direction::Top => {
-            dy = -1;
+/// Deduces the pipe under the start from the neighbours that connect back to
+/// it. When more than two do, some of them are stray pipes that only happen
+/// to face the start, so each pair is tried until one closes a loop.
+pub fn infer_start_pipe(
+    maze: &Grid<Pipe>,
+    start: (usize, usize),
+) -> AocResult<Pipe> {
+    let connected: Vec<Direction> = Direction::ALL
+        .into_iter()
+        .filter(|direction| {
+            maze.offset(start, direction.delta())
+                .is_some_and(|next| maze[next].connects(direction.opposite()))
+        })
+        .collect();
+
+    let candidates: Vec<Pipe> = connected
+        .iter()
+        .enumerate()
+        .flat_map(|(i, &a)| {
+            connected[i + 1..].iter().map(move |&b| {
+                Pipe::from_directions(a, b)
+                    .expect("every pair of directions has a pipe")
+            })
+        })
+        .collect();
+
+    match candidates[..] {
+        [] => Err(AocError::unsolvable(format!(
+            "the start connects to {} pipes instead of 2",
+            connected.len()
+        ))),
+        [pipe] => Ok(pipe),
+        _ => {
+            let mut maze = maze.clone();
+            candidates
+                .into_iter()
+                .find(|&pipe| {
+                    maze[start] = pipe;
+                    walk_loop(&maze, start).is_ok()
+                })
+                .ok_or_else(|| {
+                    AocError::unsolvable(format!(
+                        "none of the {} pipes next to the start close a loop",
+                        connected.len()
+                    ))
+                })
         }
-        Direction::Right => {
-            dx = 1;
-        }
-        Direction::Down => {
-            dy = 1;
-        }
-        Direction::Left => {
-            dx = -1;
+    }
+}
+
+/// Walks the loop through `start`, returning every tile of it in order and
+/// ending back at the start.
+pub fn walk_loop(
+    maze: &Grid<Pipe>,
+    start: (usize, usize),
+) -> AocResult<Vec<(i64, i64)>> {
+    let mut position = start;
+    let (mut direction, _) = maze[start]
+        .get_directions()
+        .ok_or_else(|| AocError::unsolvable("there is no pipe at the start"))?;
+    let mut polygon: Vec<(i64, i64)> = Vec::new();
+
+    loop {
+        position = maze
+            .offset(position, direction.delta())
+            .ok_or_else(left_the_maze)?;
+        polygon.push((position.0 as i64, position.1 as i64));
+
+        if position == start {
+            // The loop must come back in through the other end of the start
+            if !maze[start].connects(direction.opposite()) {
+                return Err(AocError::unsolvable(
+                    "the loop does not close at the start",
+                ));
+            }
+            break;
         }
-        _ => (),
+
+        let came_from = direction.opposite();
+        direction = match maze[position].get_directions() {
+            Some((a, b)) if a == came_from => b,
+            Some((a, b)) if b == came_from => a,
+            _ => {
+                return Err(AocError::unsolvable(format!(
+                    "the loop is broken at ({}, {})",
+                    position.0, position.1
+                )))
+            }
+        };
     }
 
-    (current_position.y as i32 + dy) as usize == last_position.y
-        && (current_position.x as i32 + dx) as usize == last_position.x
+    Ok(polygon)
 }
 
-fn is_point_in_polygon(point: &(f64, f64), polygon: &Vec<(f64, f64)>) -> bool {
-    // ray tracing
-    let mut count = 0;
+/// Steps along the loop to the tile farthest away from the start, which is
+/// halfway around it either way.
+pub fn farthest_steps(polygon: &[(i64, i64)]) -> i32 {
+    (polygon.len() / 2) as i32
+}
 
-    for i in 0..polygon.len() {
-        let j = (i + 1) % polygon.len();
-        let pi = &polygon[i];
-        let pj = &polygon[j];
+/// The tiles enclosed by the loop.
+pub fn enclosed_tiles(polygon: &[(i64, i64)]) -> i32 {
+    // The loop only moves between neighbouring tiles, so its tiles are
+    // exactly the boundary points and the enclosed tiles the interior ones
+    interior_points(polygon) as i32
+}
 
-        if (pi.1 > point.1) != (pj.1 > point.1)
-            && point.0 < (pj.0 - pi.0) * (point.1 - pi.1) / (pj.1 - pi.1) + pi.0
-        {
-            count += 1;
-        }
-    }
+/// The pipes with the start replaced by the pipe under it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Maze {
+    pub pipes: Grid<Pipe>,
+    pub start: (usize, usize),
+}
 
-    count % 2 == 1
+pub fn process_file(file_path: &str) -> AocResult<(i32, i32)> {
+    let file = File::open(file_path)?;
+    process_reader(io::BufReader::new(file))
 }
 
-fn explore_maze(
-    matrix: &Vec<Vec<char>>,
-    start_coordinate: (usize, usize),
-) -> (i32, i32) {
-    let mut current_position =
-        Point::new(start_coordinate.0, start_coordinate.1, Pipe::StartPosition);
-    let mut last_position: Point;
-    let mut count = 0;
-    let mut enclosed_count = 0;
-    let mut polygon: Vec<(f64, f64)> =
-        vec![(current_position.x as f64, current_position.y as f64)];
-
-    let start_directions = get_start_directions(matrix, &current_position);
-    // println!("start_directions: {:?}", start_directions);
-
-    let mut found = false;
-    for start_direction in start_directions.iter() {
-        // first traversal
-        last_position = current_position.clone();
-        let new_coordinate =
-            traverse(&mut current_position, &matrix, start_direction);
-        polygon.push(new_coordinate);
-        count += 1;
-
-        loop {
-            let next_pipe = current_position.pipe.clone();
-            let new_directions: (Direction, Direction) =
-                next_pipe.get_directions();
-            // println!("next_pipe: {:?}", next_pipe);
-
-            for direction in
-                new_directions.0.iter().chain(new_directions.1.iter())
-            {
-                let is_next_point_ok = peek_next_point(
-                    &current_position,
-                    &last_position,
-                    direction,
-                );
-                if !is_next_point_ok {
-                    // println!("go this direction next {:?}", direction);
-                    last_position = current_position.clone();
-                    let new_coordinate =
-                        traverse(&mut current_position, &matrix, direction);
-                    polygon.push(new_coordinate);
-                    count += 1;
-                    break;
-                }
-            }
+pub fn process_str(input: &str) -> AocResult<(i32, i32)> {
+    process_reader(input.as_bytes())
+}
 
-            if current_position.x == start_coordinate.0
-                && current_position.y == start_coordinate.1
-            {
-                // println!("reached start position");
-                found = true;
-                break;
-            }
-        }
+pub fn process_reader(reader: impl BufRead) -> AocResult<(i32, i32)> {
+    let maze = read_maze(reader)?;
+    let polygon = walk_loop(&maze.pipes, maze.start)?;
 
-        if found {
-            break;
-        }
-    }
+    Ok((farthest_steps(&polygon), enclosed_tiles(&polygon)))
+}
 
-    for y in 0..matrix.len() {
-        for x in 0..matrix[y].len() {
-            if !polygon.contains(&(x as f64, y as f64))
-                && is_point_in_polygon(&(x as f64, y as f64), &polygon)
-            {
-                enclosed_count += 1;
-                // println!("enclosed polygon: {:?}", &(x as f64, y as f64));
-            }
-        }
+/// Parses the tiles and replaces the start `S` with the pipe under it.
+pub fn read_maze(reader: impl BufRead) -> AocResult<Maze> {
+    // `None` marks the start
+    let tiles = Grid::from_reader(reader, |c| match c {
+        'S' => Ok(None),
+        _ => Pipe::from_char(c)
+            .map(Some)
+            .ok_or("expected one of `|-LJ7F.S`"),
+    })?;
+
+    let mut starts = tiles
+        .iter()
+        .filter_map(|(position, tile)| tile.is_none().then_some(position));
+    let start = starts.next().ok_or(AocError::MissingStart('S'))?;
+    if let Some((x, y)) = starts.next() {
+        return Err(AocError::parse(y + 1, x + 1, "expected only one `S`"));
     }
 
-    // println!("polygon: {:?}", polygon);
-    // println!("enclosed_count: {}", enclosed_count);
+    let mut pipes = tiles.map(|tile| tile.unwrap_or(Pipe::Ground));
+    pipes[start] = infer_start_pipe(&pipes, start)?;
 
-    (count / 2, enclosed_count)
+    Ok(Maze { pipes, start })
 }
 
-pub fn process_file(file_path: &str) -> (i32, i32) {
-    if let Ok(file) = File::open(file_path) {
-        let reader = io::BufReader::new(file);
+fn left_the_maze() -> AocError {
+    AocError::unsolvable("the loop leaves the maze")
+}
 
-        // Initialize a vector to store vectors of characters (lines)
-        let mut matrix: Vec<Vec<char>> = Vec::new();
-        let mut start_coordinate: Option<(usize, usize)> = None;
+pub struct Day10;
 
-        for (row, line) in reader.lines().enumerate() {
-            if let Ok(line_content) = line {
-                let char_vector: Vec<char> = line_content.chars().collect();
-                matrix.push(char_vector);
+impl Solution for Day10 {
+    type Input = Maze;
 
-                // Check for 'S' and store its coordinate
-                if let Some(col) = matrix[row].iter().position(|&c| c == 'S') {
-                    start_coordinate = Some((col, row));
-                }
-            }
-        }
-
-        // for row in matrix.iter() {
-        //     println!("{:?}", row);
-        // }
+    fn parse(&self, input: &str) -> AocResult<Self::Input> {
+        read_maze(input.as_bytes())
+    }
 
-        if let Some((col, row)) = start_coordinate {
-            // println!("Start coordinate: ({}, {})", col, row);
+    fn part1(&self, maze: &Self::Input) -> AocResult<Answer> {
+        let polygon = walk_loop(&maze.pipes, maze.start)?;
+        Ok(farthest_steps(&polygon).into())
+    }
 
-            // Explore the maze starting from the given coordinate
-            explore_maze(&matrix, (col, row))
-        } else {
-            // println!("No 'S' found in the matrix.");
-            (0, 0)
-        }
-    } else {
-        eprintln!("Error opening file: {}", file_path);
-        (0, 0)
+    fn part2(&self, maze: &Self::Input) -> AocResult<Answer> {
+        let polygon = walk_loop(&maze.pipes, maze.start)?;
+        Ok(enclosed_tiles(&polygon).into())
     }
 }
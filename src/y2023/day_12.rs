@@ -1,88 +1,124 @@
+use crate::error::{parse_token, AocError, AocResult};
+use crate::solution::{Answer, Solution};
 use rayon::prelude::*;
 use std::fs::File;
 use std::io::{self, BufRead};
 
-fn generate_cases(
-    chars: &mut Vec<char>,
-    index: usize,
-    results: &mut Vec<String>,
-) {
-    if index == chars.len() {
-        let result_str: String = chars.iter().collect();
-        if !results.contains(&result_str) {
-            results.push(result_str);
-        }
-        return;
+/// Row of spring conditions alongside the sizes of its damaged groups.
+pub type Row = (String, Vec<usize>);
+
+/// Counts the ways to replace every `?` in `records` with `.` or `#` so that
+/// the runs of `#` have exactly the sizes in `groups`, in order.
+///
+/// Each state is the position in `records`, the index of the group being
+/// filled and the length of the current run of `#`, so each of the roughly
+/// `records.len() * groups.len() * max(groups)` states is counted once.
+/// Groups that cannot fit into the records are ruled out before any state is
+/// set up.
+pub fn count_arrangements(records: &str, groups: &[usize]) -> AocResult<u64> {
+    // Every group needs its springs plus one to separate it from the next,
+    // which the last group gets for free at the end of the records
+    let needed = groups.iter().try_fold(0usize, |needed, &size| {
+        needed.checked_add(size)?.checked_add(1)
+    });
+    if needed.map_or(true, |needed| needed > records.len() + 1) {
+        return Ok(0);
     }
 
-    if chars[index] == '?' {
-        chars[index] = '.';
-        generate_cases(chars, index + 1, results);
-        chars[index] = '#';
-        generate_cases(chars, index + 1, results);
-        chars[index] = '?'; // Reset back to "?" for backtracking
-    } else {
-        generate_cases(chars, index + 1, results);
+    let longest = groups.iter().copied().max().unwrap_or(0);
+    let states = (records.len() + 1)
+        .checked_mul(groups.len() + 1)
+        .and_then(|states| states.checked_mul(longest + 1))
+        .ok_or_else(|| {
+            AocError::unsolvable(format!(
+                "a row of {} springs has too many states to count",
+                records.len()
+            ))
+        })?;
+
+    Ok(Arrangements {
+        records: records.as_bytes(),
+        groups,
+        longest,
+        memo: vec![None; states],
     }
+    .count(0, 0, 0))
 }
 
-fn generate_cases_iterative(chars: &mut Vec<char>, results: &mut Vec<String>) {
-    let mut stack: Vec<(usize, Vec<char>)> = Vec::new();
+struct Arrangements<'a> {
+    records: &'a [u8],
+    groups: &'a [usize],
+    longest: usize,
+    memo: Vec<Option<u64>>,
+}
 
-    stack.push((0, chars.clone()));
+impl Arrangements<'_> {
+    fn count(&mut self, position: usize, group: usize, run: usize) -> u64 {
+        let key = (position * (self.groups.len() + 1) + group)
+            * (self.longest + 1)
+            + run;
+        if let Some(count) = self.memo[key] {
+            return count;
+        }
 
-    while let Some((index, current_chars)) = stack.pop() {
-        if index == chars.len() {
-            let result_str: String = current_chars.iter().collect();
-            if !results.contains(&result_str) {
-                results.push(result_str);
+        let groups = self.groups;
+        let count = match self.records.get(position) {
+            // Every group must be closed off, the last one possibly by the end
+            None if run == 0 => u64::from(group == groups.len()),
+            None => {
+                u64::from(group + 1 == groups.len() && run == groups[group])
             }
-        } else if current_chars[index] == '?' {
-            for &replace_char in &['.', '#'] {
-                let mut new_chars = current_chars.clone();
-                new_chars[index] = replace_char;
-                stack.push((index + 1, new_chars));
-            }
-        } else {
-            stack.push((index + 1, current_chars));
-        }
-    }
-}
+            Some(&spring) => {
+                let mut count = 0;
+
+                if spring != b'.' && group < groups.len() && run < groups[group]
+                {
+                    count += self.count(position + 1, group, run + 1);
+                }
+                if spring != b'#' {
+                    if run == 0 {
+                        count += self.count(position + 1, group, 0);
+                    } else if run == groups[group] {
+                        count += self.count(position + 1, group + 1, 0);
+                    }
+                }
 
-fn count_damages(input: &str) -> Vec<usize> {
-    input
-        .split('.')
-        .map(|substring| substring.chars().filter(|&c| c == '#').count())
-        .filter(|&count| count > 0)
-        .collect()
-}
+                count
+            }
+        };
 
-fn compare_vectors(vec1: &[usize], vec2: &[usize]) -> bool {
-    if vec1.len() != vec2.len() {
-        return false;
+        self.memo[key] = Some(count);
+        count
     }
-
-    vec1.iter().zip(vec2.iter()).all(|(&a, &b)| a == b)
 }
 
-// fn compare_vectors(vec1: &[usize], vec2: &[usize]) -> bool {
-//     vec1.iter().eq(vec2.iter())
-// }
-
-fn parse_line(line: &str) -> (String, Vec<usize>) {
-    let parts: Vec<&str> = line.split_whitespace().collect();
-
-    let records: String = parts[0].to_string();
+fn parse_line(line_number: usize, line: &str) -> AocResult<Row> {
+    let Some((records, conditions)) = line.split_once(' ') else {
+        return Err(AocError::parse(
+            line_number,
+            line.len() + 1,
+            "expected `<records> <group sizes>`",
+        ));
+    };
+
+    if let Some(col) = records.find(|c| !matches!(c, '.' | '#' | '?')) {
+        return Err(AocError::parse(
+            line_number,
+            col + 1,
+            "expected `.`, `#` or `?`",
+        ));
+    }
 
-    let conditions: Vec<usize> = parts[1]
+    let conditions = conditions
+        .trim()
         .split(',')
-        .flat_map(|s| s.parse::<usize>())
-        .collect();
+        .map(|s| parse_token(line_number, line, s))
+        .collect::<AocResult<Vec<usize>>>()?;
 
-    (records, conditions)
+    Ok((records.to_string(), conditions))
 }
 
-fn unfold(records: &str, conditions: &[usize]) -> (String, Vec<usize>) {
+pub fn unfold(records: &str, conditions: &[usize]) -> Row {
     let unfolded_records: String = std::iter::repeat(records)
         .take(5)
         .collect::<Vec<&str>>()
@@ -96,49 +132,56 @@ fn unfold(records: &str, conditions: &[usize]) -> (String, Vec<usize>) {
     (unfolded_records, unfolded_conditions)
 }
 
-pub fn process_file(file_path: &str) -> usize {
-    if let Ok(file) = File::open(file_path) {
-        let reader = io::BufReader::new(file);
-
-        let sum = reader
-            .lines()
-            .par_bridge()
-            .map(|line| {
-                let line = line.unwrap();
-                let (records, conditions) = parse_line(&line);
-                // let (records, conditions) = unfold(&records, &conditions);
-                // println!("Records: {:?}, Conditions: {:?}", records, conditions);
-
-                let mut chars: Vec<char> = records.chars().collect();
-                let mut results: Vec<String> = Vec::new();
-
-                // generate_cases(&mut chars, 0, &mut results);
-                generate_cases_iterative(&mut chars, &mut results);
-
-                let count = results
-                    .par_iter()
-                    .filter(|case| {
-                        let is_matching =
-                            compare_vectors(&count_damages(case), &conditions);
-                        if is_matching {
-                            println!(
-                                "case {:?} matching {:?} ? {}",
-                                case, conditions, is_matching
-                            );
-                        }
-                        is_matching
-                    })
-                    .count();
-
-                println!("count for {:?} : {}", records, count);
-                count
-            })
-            .reduce(|| 0, |a, b| a + b);
-
-        println!("Total sum: {}", sum);
-        sum
-    } else {
-        eprintln!("Error opening file: {}", file_path);
-        0
+pub fn process_file(file_path: &str) -> AocResult<Vec<Row>> {
+    let file = File::open(file_path)?;
+    process_reader(io::BufReader::new(file))
+}
+
+pub fn process_str(input: &str) -> AocResult<Vec<Row>> {
+    process_reader(input.as_bytes())
+}
+
+pub fn process_reader(reader: impl BufRead) -> AocResult<Vec<Row>> {
+    let mut rows = Vec::new();
+    for (index, line) in reader.lines().enumerate() {
+        let line = line?;
+        if !line.trim().is_empty() {
+            rows.push(parse_line(index + 1, &line)?);
+        }
+    }
+
+    Ok(rows)
+}
+
+pub fn sum_arrangements(rows: &[Row]) -> AocResult<u64> {
+    rows.par_iter()
+        .map(|(records, conditions)| count_arrangements(records, conditions))
+        .sum()
+}
+
+pub fn sum_unfolded_arrangements(rows: &[Row]) -> AocResult<u64> {
+    rows.par_iter()
+        .map(|(records, conditions)| {
+            let (records, conditions) = unfold(records, conditions);
+            count_arrangements(&records, &conditions)
+        })
+        .sum()
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input = Vec<Row>;
+
+    fn parse(&self, input: &str) -> AocResult<Self::Input> {
+        process_str(input)
+    }
+
+    fn part1(&self, rows: &Self::Input) -> AocResult<Answer> {
+        Ok(sum_arrangements(rows)?.into())
+    }
+
+    fn part2(&self, rows: &Self::Input) -> AocResult<Answer> {
+        Ok(sum_unfolded_arrangements(rows)?.into())
     }
 }
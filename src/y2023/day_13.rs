@@ -1,159 +1,168 @@
+use crate::error::{AocError, AocResult};
+use crate::solution::{Answer, Solution};
+use crate::trace;
+use crate::utils::grid::Grid;
 use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::io::{self, BufRead, BufReader};
 
-pub fn process_file(
-    filename: &str,
-) -> Vec<(Vec<Vec<char>>, Option<usize>, Option<usize>)> {
-    let file = File::open(filename).expect("Unable to open the file.");
-    let reader = BufReader::new(file);
+/// Rocks are set bits, so comparing two rows or columns is a single XOR.
+const MAX_SIDE: usize = u64::BITS as usize;
 
-    let mut result = Vec::new();
-    let mut current_block = Vec::new();
-
-    for line in reader.lines() {
-        if let Ok(row) = line {
-            if row.is_empty() {
-                // Empty line indicates the end of a block, so add the current_block to the result
-                process_block(&mut result, &mut current_block);
-            } else {
-                // Non-empty line, add each char to the current_block
-                current_block.push(row.chars().collect());
-            }
-        }
-    }
-
-    // Add the last block if it's not empty
-    process_block(&mut result, &mut current_block);
-
-    result
+/// A pattern of ash and rocks with every row and column packed into a bitmask.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Block {
+    pub grid: Grid<char>,
+    rows: Vec<u64>,
+    columns: Vec<u64>,
 }
 
-fn process_block(
-    result: &mut Vec<(Vec<Vec<char>>, Option<usize>, Option<usize>)>,
-    current_block: &mut Vec<Vec<char>>,
-) {
-    if !current_block.is_empty() {
-        let transposed = transpose_matrix(&current_block);
-        let horizontal_reflection = find_vertical_reflection(&transposed);
-        let vertical_reflection = find_vertical_reflection(&current_block);
-
-        result.push((
-            current_block.clone(),
-            horizontal_reflection,
-            vertical_reflection,
-        ));
-        current_block.clear();
+impl Block {
+    fn new(grid: Grid<char>) -> Self {
+        let rows = grid.rows().map(|row| mask(row.iter())).collect();
+        let columns = (0..grid.width()).map(|x| mask(grid.column(x))).collect();
+
+        Block {
+            grid,
+            rows,
+            columns,
+        }
     }
-}
 
-pub fn transpose_matrix(matrix: &Vec<Vec<char>>) -> Vec<Vec<char>> {
-    let rows = matrix.len();
-    let cols = matrix[0].len();
+    /// Rows above the horizontal line whose reflection differs in exactly
+    /// `smudges` cells.
+    pub fn horizontal_reflection(&self, smudges: u32) -> Option<usize> {
+        find_reflection(&self.rows, smudges)
+    }
 
-    // Create a new matrix with swapped rows and columns
-    let mut transposed_matrix = vec![vec!['0'; rows]; cols];
+    /// Columns left of the vertical line whose reflection differs in exactly
+    /// `smudges` cells.
+    pub fn vertical_reflection(&self, smudges: u32) -> Option<usize> {
+        find_reflection(&self.columns, smudges)
+    }
 
-    for i in 0..rows {
-        for j in 0..cols {
-            transposed_matrix[j][i] = matrix[i][j];
+    /// 100 times the rows above the horizontal line plus the columns left of
+    /// the vertical line, or `None` if the block has neither.
+    pub fn summary(&self, smudges: u32) -> Option<usize> {
+        match (
+            self.horizontal_reflection(smudges),
+            self.vertical_reflection(smudges),
+        ) {
+            (None, None) => None,
+            (rows, columns) => {
+                Some(100 * rows.unwrap_or(0) + columns.unwrap_or(0))
+            }
         }
     }
-
-    transposed_matrix
 }
 
-pub fn find_vertical_reflection(block: &Vec<Vec<char>>) -> Option<usize> {
-    let columns = block[0].len();
-    if columns < 2 {
-        return None;
-    }
+fn mask<'a>(line: impl Iterator<Item = &'a char>) -> u64 {
+    line.fold(0, |mask, &cell| mask << 1 | u64::from(cell == '#'))
+}
 
-    for i in 0..columns {
-        let mut left = i as i32;
-        let mut right = left + 1;
-
-        'this_loop: while left >= 0 && right <= columns as i32 - 1 {
-            for row in block {
-                let mut temp_left = left;
-                let mut temp_right = right;
-
-                while temp_left >= 0 && temp_right <= columns as i32 - 1 {
-                    // println!(
-                    //     "i {}, left {}, right {}, row {:?}, {} vs {}",
-                    //     i,
-                    //     temp_left,
-                    //     temp_right,
-                    //     row,
-                    //     row[temp_left as usize],
-                    //     row[temp_right as usize]
-                    // );
-                    if row[temp_left as usize] == row[temp_right as usize] {
-                        temp_left -= 1;
-                        temp_right += 1;
-                    } else {
-                        break 'this_loop;
-                    }
-                }
-            }
+pub fn process_file(filename: &str) -> AocResult<Vec<Block>> {
+    let file = File::open(filename)?;
+    process_reader(BufReader::new(file))
+}
 
-            // Increment/decrement indices for the next comparison
-            left -= 1;
-            right += 1;
+pub fn process_str(input: &str) -> AocResult<Vec<Block>> {
+    process_reader(input.as_bytes())
+}
 
-            // If the loop completes without breaking, return i
-            if left < 0 || right > columns as i32 - 1 {
-                return Some(i);
+pub fn process_reader(reader: impl BufRead) -> AocResult<Vec<Block>> {
+    let lines = reader.lines().collect::<io::Result<Vec<String>>>()?;
+    let mut result = Vec::new();
+    let mut first_line = 1;
+
+    // Blocks are separated by empty lines
+    for block in lines.split(|line| line.is_empty()) {
+        if !block.is_empty() {
+            let grid = Grid::from_lines(block, first_line, |c| match c {
+                '.' | '#' => Ok(c),
+                _ => Err("expected `.` or `#`"),
+            })?;
+
+            if grid.width() > MAX_SIDE {
+                return Err(AocError::parse(
+                    first_line,
+                    MAX_SIDE + 1,
+                    format!("blocks must be at most {} wide", MAX_SIDE),
+                ));
+            }
+            if grid.height() > MAX_SIDE {
+                return Err(AocError::parse(
+                    first_line + MAX_SIDE,
+                    1,
+                    format!("blocks must be at most {} high", MAX_SIDE),
+                ));
             }
+
+            result.push(Block::new(grid));
         }
+        first_line += block.len() + 1;
     }
 
-    None // Return None if no reflection line is found
+    Ok(result)
 }
 
-pub fn smudge_block(
-    block: &Vec<Vec<char>>,
-    i: usize,
-    j: usize,
-) -> Vec<Vec<char>> {
-    let mut new_block = block.clone();
-
-    // Change the element at the specified position
-    new_block[i][j] = if new_block[i][j] == '#' { '.' } else { '#' };
+/// The number of lines before the first mirror between two lines for which
+/// the mirrored pairs of lines differ in exactly `smudges` cells in total.
+/// Pairs reaching past either edge are not compared.
+pub fn find_reflection(lines: &[u64], smudges: u32) -> Option<usize> {
+    (1..lines.len()).find(|&before| {
+        let mut mismatches = 0;
+
+        for (above, below) in lines[..before].iter().rev().zip(&lines[before..])
+        {
+            mismatches += (above ^ below).count_ones();
+            if mismatches > smudges {
+                return false;
+            }
+        }
 
-    new_block
+        mismatches == smudges
+    })
 }
 
-pub fn get_sum(
-    result: &Vec<(Vec<Vec<char>>, Option<usize>, Option<usize>)>,
-) -> usize {
+/// Sums up the summaries of every block, with reflections that have exactly
+/// `smudges` smudges on them.
+pub fn get_sum(blocks: &[Block], smudges: u32) -> AocResult<usize> {
     let mut sum = 0;
 
-    // Print the result in 2D format along with reflection lines
-    for (block, horizontal_reflection, vertical_reflection) in result {
-        println!("Block:");
-        for row in block {
-            for ch in row {
-                print!("{} ", ch);
-            }
-            println!();
-        }
+    for (index, block) in blocks.iter().enumerate() {
+        let summary = block.summary(smudges).ok_or_else(|| {
+            AocError::unsolvable(format!(
+                "block {} has no reflection with {} smudge(s)",
+                index + 1,
+                smudges
+            ))
+        })?;
+        trace!(
+            "block {}: horizontal {:?}, vertical {:?}, summary {}",
+            index + 1,
+            block.horizontal_reflection(smudges),
+            block.vertical_reflection(smudges),
+            summary
+        );
+        sum += summary;
+    }
 
-        if let Some(h_line) = horizontal_reflection {
-            println!("Horizontal Reflection Line: Row {}", h_line);
-            sum += 100 * (h_line + 1);
-        } else {
-            println!("No Horizontal Reflection Line");
-        }
+    Ok(sum)
+}
 
-        if let Some(v_line) = vertical_reflection {
-            sum += v_line + 1;
-            println!("Vertical Reflection Line: Column {}", v_line);
-        } else {
-            println!("No Vertical Reflection Line");
-        }
+pub struct Day13;
 
-        println!();
+impl Solution for Day13 {
+    type Input = Vec<Block>;
+
+    fn parse(&self, input: &str) -> AocResult<Self::Input> {
+        process_str(input)
+    }
+
+    fn part1(&self, blocks: &Self::Input) -> AocResult<Answer> {
+        Ok(get_sum(blocks, 0)?.into())
     }
 
-    sum
+    fn part2(&self, blocks: &Self::Input) -> AocResult<Answer> {
+        Ok(get_sum(blocks, 1)?.into())
+    }
 }
@@ -1,158 +1,228 @@
+use crate::error::{AocError, AocResult};
+use crate::solution::{Answer, Solution};
+use crate::utils::cycle::nth_state;
+use crate::utils::grid::Grid;
+use std::fmt;
 use std::fs::File;
 use std::io::{BufRead, BufReader};
 
-pub fn process_file(filename: &str) -> Vec<Vec<char>> {
-    let file = File::open(filename).expect("Unable to open the file.");
-    let reader = BufReader::new(file);
+/// Every row is packed into one bitmask, with column `x` at bit `x`.
+const MAX_WIDTH: usize = u128::BITS as usize;
 
-    let mut result = Vec::new();
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tilt {
+    North,
+    West,
+    South,
+    East,
+}
 
-    for line in reader.lines() {
-        if let Ok(row) = line {
-            let mut current_row = Vec::new();
+impl Tilt {
+    /// The order of the tilts in one spin cycle.
+    pub const SPIN_CYCLE: [Tilt; 4] =
+        [Tilt::North, Tilt::West, Tilt::South, Tilt::East];
+}
 
-            for ch in row.chars() {
-                current_row.push(ch);
-            }
+/// The platform with its rounded and cube-shaped rocks stored as one pair of
+/// bit rows per row.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Platform {
+    width: usize,
+    round: Vec<u128>,
+    cubes: Vec<u128>,
+}
 
-            result.push(current_row);
+impl Platform {
+    /// # Panics
+    ///
+    /// Panics if the grid is wider than 128 cells.
+    pub fn from_grid(grid: &Grid<char>) -> Self {
+        assert!(
+            grid.width() <= MAX_WIDTH,
+            "grid is wider than {}",
+            MAX_WIDTH
+        );
+
+        let bits = |row: &[char], rock: char| {
+            row.iter()
+                .enumerate()
+                .filter(|&(_, &cell)| cell == rock)
+                .fold(0u128, |bits, (x, _)| bits | 1 << x)
+        };
+
+        Platform {
+            width: grid.width(),
+            round: grid.rows().map(|row| bits(row, 'O')).collect(),
+            cubes: grid.rows().map(|row| bits(row, '#')).collect(),
         }
     }
 
-    result
-}
+    pub fn to_grid(&self) -> Grid<char> {
+        Grid::from_fn(self.width, self.height(), |x, y| {
+            if self.round[y] >> x & 1 == 1 {
+                'O'
+            } else if self.cubes[y] >> x & 1 == 1 {
+                '#'
+            } else {
+                '.'
+            }
+        })
+    }
 
-pub fn rotate_mirror_clockwise(mirror: &Vec<Vec<char>>) -> Vec<Vec<char>> {
-    let rows = mirror.len();
-    let cols = mirror[0].len();
+    pub fn width(&self) -> usize {
+        self.width
+    }
 
-    let mut rotated_mirror = vec![vec![' '; rows]; cols];
+    pub fn height(&self) -> usize {
+        self.round.len()
+    }
 
-    for i in 0..rows {
-        for j in 0..cols {
-            rotated_mirror[j][rows - 1 - i] = mirror[i][j];
+    /// Rolls every rounded rock as far as it goes in the given direction.
+    pub fn tilt(&mut self, tilt: Tilt) {
+        match tilt {
+            Tilt::North => self.tilt_vertically(false),
+            Tilt::South => self.tilt_vertically(true),
+            Tilt::West => self.tilt_horizontally(false),
+            Tilt::East => self.tilt_horizontally(true),
         }
     }
 
-    rotated_mirror
-}
+    pub fn spin_cycle(&mut self) {
+        for tilt in Tilt::SPIN_CYCLE {
+            self.tilt(tilt);
+        }
+    }
+
+    /// Load on the north support beams.
+    pub fn load(&self) -> usize {
+        self.round
+            .iter()
+            .enumerate()
+            .map(|(y, row)| (self.height() - y) * row.count_ones() as usize)
+            .sum()
+    }
 
-pub fn find_cycle_length(mirror: &Vec<Vec<char>>) -> (usize, usize) {
-    let mut tortoise = mirror.clone();
-    let mut hare = mirror.clone();
-
-    let mut tortoise_load_seq = Vec::new();
-    let mut hare_load_seq = Vec::new();
-
-    let mut cycle_length = 0;
-    let mut cycle_start = 0;
-
-    loop {
-        // Move the tortoise one step
-        tortoise = rotate_one_cycle(&tortoise);
-        tortoise_load_seq.push(measure_load(&tortoise));
-
-        // Move the hare two steps
-        hare = rotate_one_cycle(&hare);
-        hare = rotate_one_cycle(&hare);
-        hare_load_seq.push(measure_load(&hare));
-
-        if hare_load_seq.len() % 2 == 0 {
-            // Check for cycle after every two steps of hare
-            if hare_load_seq[cycle_start]
-                == hare_load_seq[hare_load_seq.len() - 1]
-            {
-                // Cycle detected, find the length and start index
-                let mut i = cycle_start;
-                while hare_load_seq[i] != hare_load_seq[hare_load_seq.len() - 1]
-                {
-                    i += 1;
-                }
-                cycle_length = hare_load_seq.len() - cycle_start;
-                cycle_start = i;
-                break;
+    /// Settles the rows one by one starting from the side the rocks roll to,
+    /// so all rows in front of the current one are already packed and a
+    /// whole row of rocks can move in step until each one is blocked.
+    fn tilt_vertically(&mut self, south: bool) {
+        let height = self.height();
+        // Row `i` counted from the side the rocks roll to
+        let row = |i: usize| if south { height - 1 - i } else { i };
+
+        for i in 1..height {
+            let mut moving = std::mem::take(&mut self.round[row(i)]);
+            let mut at = i;
+
+            while moving != 0 && at > 0 {
+                let ahead = row(at - 1);
+                let blocked = self.round[ahead] | self.cubes[ahead];
+                self.round[row(at)] |= moving & blocked;
+                moving &= !blocked;
+                at -= 1;
             }
+            self.round[row(at)] |= moving;
         }
+    }
 
-        if tortoise_load_seq == hare_load_seq {
-            // Cycle detected, find the length and start index
-            let mut i = cycle_start;
-            while hare_load_seq[i] != hare_load_seq[hare_load_seq.len() - 1] {
-                i += 1;
+    /// Packs the rocks of every stretch between two cube-shaped rocks
+    /// against the stretch's west or east end.
+    fn tilt_horizontally(&mut self, east: bool) {
+        for (round, &cubes) in self.round.iter_mut().zip(&self.cubes) {
+            let mut free = !cubes & stretch(0, self.width);
+            let mut packed = 0;
+
+            while free != 0 {
+                let start = free.trailing_zeros() as usize;
+                let end = start + (free >> start).trailing_ones() as usize;
+                let count =
+                    (*round & stretch(start, end)).count_ones() as usize;
+
+                packed |= if east {
+                    stretch(end - count, end)
+                } else {
+                    stretch(start, start + count)
+                };
+                free &= !stretch(start, end);
             }
-            cycle_length = hare_load_seq.len() - cycle_start;
-            cycle_start = i;
-            break;
+
+            *round = packed;
         }
     }
+}
 
-    (cycle_length, cycle_start)
+/// The bits from `start` up to but excluding `end`.
+fn stretch(start: usize, end: usize) -> u128 {
+    if start >= end {
+        return 0;
+    }
+    (u128::MAX >> (MAX_WIDTH - (end - start))) << start
 }
 
-pub fn rotate_one_cycle(mirror: &Vec<Vec<char>>) -> Vec<Vec<char>> {
-    let mut new_mirror: Vec<Vec<char>> = mirror.clone();
-    // one cycle
-    // North -> West -> South -> East
-    for _ in 1..=4 {
-        new_mirror = tilt_north(&new_mirror);
-        new_mirror = rotate_mirror_clockwise(&new_mirror);
+impl fmt::Display for Platform {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_grid())
     }
+}
 
-    new_mirror
+pub fn process_file(filename: &str) -> AocResult<Platform> {
+    let file = File::open(filename)?;
+    process_reader(BufReader::new(file))
 }
 
-pub fn tilt_north(mirror: &Vec<Vec<char>>) -> Vec<Vec<char>> {
-    let mut result: Vec<Vec<char>> = Vec::new();
-    let col_len = mirror[0].len();
+pub fn process_str(input: &str) -> AocResult<Platform> {
+    process_reader(input.as_bytes())
+}
 
-    for mirror in mirror.iter() {
-        result.push(mirror.clone());
+pub fn process_reader(reader: impl BufRead) -> AocResult<Platform> {
+    let grid = Grid::from_reader(reader, |c| match c {
+        'O' | '#' | '.' => Ok(c),
+        _ => Err("expected `O`, `#` or `.`"),
+    })?;
+
+    if grid.width() > MAX_WIDTH {
+        return Err(AocError::parse(
+            1,
+            MAX_WIDTH + 1,
+            format!("the platform must be at most {} wide", MAX_WIDTH),
+        ));
+    }
 
-        for col in 0..col_len {
-            let end = result.len() - 1;
-            let mut i = result.len() - 1;
-            if i == 0 {
-                break;
-            }
+    Ok(Platform::from_grid(&grid))
+}
 
-            if result[end][col] == '#' || result[end][col] == '.' {
-                continue;
-            }
+/// Load on the north beams after tilting the platform north once.
+pub fn measure_north_load(platform: &Platform) -> usize {
+    let mut platform = platform.clone();
+    platform.tilt(Tilt::North);
+    platform.load()
+}
 
-            while i > 0 {
-                i -= 1;
-                if result[i][col] == '#' || result[i][col] == 'O' {
-                    if i + 1 == end {
-                        break;
-                    }
-                    // swap
-                    let temp = result[end][col];
-                    result[end][col] = result[i + 1][col];
-                    result[i + 1][col] = temp;
-                    break;
-                } else if i == 0 {
-                    // swap
-                    let temp = result[end][col];
-                    result[end][col] = result[i][col];
-                    result[i][col] = temp;
-                    break;
-                }
-            }
-        }
-    }
+/// Load on the north beams after `cycles` spin cycles.
+pub fn measure_load_after(platform: &Platform, cycles: usize) -> usize {
+    let spin = |platform: &Platform| {
+        let mut platform = platform.clone();
+        platform.spin_cycle();
+        platform
+    };
 
-    result
+    nth_state(platform.clone(), spin, cycles).load()
 }
 
-pub fn measure_load(mirror: &Vec<Vec<char>>) -> usize {
-    let mut total_load = 0;
+pub struct Day14;
+
+impl Solution for Day14 {
+    type Input = Platform;
 
-    for (i, row) in mirror.iter().enumerate() {
-        total_load += (mirror.len() - i) * count_o(row);
+    fn parse(&self, input: &str) -> AocResult<Self::Input> {
+        process_str(input)
+    }
+
+    fn part1(&self, platform: &Self::Input) -> AocResult<Answer> {
+        Ok(measure_north_load(platform).into())
     }
-    total_load
-}
 
-fn count_o(chars: &Vec<char>) -> usize {
-    chars.iter().filter(|&&c| c == 'O').count()
+    fn part2(&self, platform: &Self::Input) -> AocResult<Answer> {
+        Ok(measure_load_after(platform, 1_000_000_000).into())
+    }
 }
@@ -0,0 +1,137 @@
+use crate::error::{column_of, parse_token, AocError, AocResult};
+use crate::solution::{Answer, Solution};
+use std::fs::File;
+use std::io::{self, BufRead};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Operation {
+    /// `label-`: take the lens out of its box
+    Remove,
+    /// `label=N`: put a lens with focal length N in its box
+    Insert(u8),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Step {
+    pub text: String,
+    pub label: String,
+    pub operation: Operation,
+}
+
+pub fn process_file(file_path: &str) -> AocResult<Vec<Step>> {
+    let file = File::open(file_path)?;
+    process_reader(io::BufReader::new(file))
+}
+
+pub fn process_str(input: &str) -> AocResult<Vec<Step>> {
+    process_reader(input.as_bytes())
+}
+
+pub fn process_reader(reader: impl BufRead) -> AocResult<Vec<Step>> {
+    let mut steps = Vec::new();
+
+    // Newlines are ignored, so a step never spans two lines
+    for (index, line) in reader.lines().enumerate() {
+        let line = line?;
+
+        for text in line.split(',').filter(|text| !text.is_empty()) {
+            steps.push(parse_step(index + 1, &line, text)?);
+        }
+    }
+
+    Ok(steps)
+}
+
+fn parse_step(line_number: usize, line: &str, text: &str) -> AocResult<Step> {
+    let (label, operation) = if let Some(label) = text.strip_suffix('-') {
+        (label, Operation::Remove)
+    } else if let Some((label, focal_length)) = text.split_once('=') {
+        let focal_length = parse_token(line_number, line, focal_length)?;
+        (label, Operation::Insert(focal_length))
+    } else {
+        return Err(AocError::parse(
+            line_number,
+            column_of(line, text),
+            format!("expected `<label>-` or `<label>=<n>`, got `{}`", text),
+        ));
+    };
+
+    if let Some(offset) = label.find(|c: char| !c.is_ascii_lowercase()) {
+        return Err(AocError::parse(
+            line_number,
+            column_of(line, label) + offset,
+            "labels must only contain lowercase letters",
+        ));
+    }
+
+    Ok(Step {
+        text: text.to_string(),
+        label: label.to_string(),
+        operation,
+    })
+}
+
+/// The Holiday ASCII String Helper algorithm.
+pub fn hash(s: &str) -> u8 {
+    s.bytes().fold(0u8, |current, byte| {
+        current.wrapping_add(byte).wrapping_mul(17)
+    })
+}
+
+pub fn sum_of_hashes(steps: &[Step]) -> u32 {
+    steps.iter().map(|step| u32::from(hash(&step.text))).sum()
+}
+
+/// Runs the HASHMAP procedure and sums up the focusing power of every lens.
+pub fn focusing_power(steps: &[Step]) -> usize {
+    let mut boxes: Vec<Vec<(&str, u8)>> = vec![Vec::new(); 256];
+
+    for step in steps {
+        let lenses = &mut boxes[hash(&step.label) as usize];
+        let slot = lenses.iter().position(|(label, _)| *label == step.label);
+
+        match (&step.operation, slot) {
+            (Operation::Remove, Some(slot)) => {
+                lenses.remove(slot);
+            }
+            (Operation::Insert(focal_length), Some(slot)) => {
+                lenses[slot].1 = *focal_length;
+            }
+            (Operation::Insert(focal_length), None) => {
+                lenses.push((&step.label, *focal_length));
+            }
+            (Operation::Remove, None) => (),
+        }
+    }
+
+    boxes
+        .iter()
+        .enumerate()
+        .flat_map(|(box_number, lenses)| {
+            lenses
+                .iter()
+                .enumerate()
+                .map(move |(slot, (_, focal_length))| {
+                    (box_number + 1) * (slot + 1) * *focal_length as usize
+                })
+        })
+        .sum()
+}
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    type Input = Vec<Step>;
+
+    fn parse(&self, input: &str) -> AocResult<Self::Input> {
+        process_str(input)
+    }
+
+    fn part1(&self, steps: &Self::Input) -> AocResult<Answer> {
+        Ok(sum_of_hashes(steps).into())
+    }
+
+    fn part2(&self, steps: &Self::Input) -> AocResult<Answer> {
+        Ok(focusing_power(steps).into())
+    }
+}
@@ -1,3 +1,5 @@
+use crate::error::{column_of, parse_token, AocError, AocResult};
+use crate::solution::{Answer, Solution};
 use std::collections::HashMap;
 use std::fs::File;
 use std::io::{self, BufRead};
@@ -15,54 +17,79 @@ pub struct Subset {
 
 pub type CubeCounts = std::collections::HashMap<String, usize>;
 
-pub fn read_games_from_file(file_path: &str) -> Result<Vec<Game>, io::Error> {
+pub fn read_games_from_file(file_path: &str) -> AocResult<Vec<Game>> {
     let file = File::open(file_path)?;
-    let reader = io::BufReader::new(file);
+    read_games(io::BufReader::new(file))
+}
+
+pub fn read_games_from_str(input: &str) -> AocResult<Vec<Game>> {
+    read_games(input.as_bytes())
+}
 
+pub fn read_games(reader: impl BufRead) -> AocResult<Vec<Game>> {
     let mut games = Vec::new();
 
-    for line in reader.lines() {
+    for (index, line) in reader.lines().enumerate() {
         let line = line?;
-        if let Some((id, subsets)) = parse_game_line(&line) {
-            games.push(Game { id, subsets });
+        if line.trim().is_empty() {
+            continue;
         }
+
+        let (id, subsets) = parse_game_line(index + 1, &line)?;
+        games.push(Game { id, subsets });
     }
 
     Ok(games)
 }
 
-fn parse_game_line(line: &str) -> Option<(usize, Vec<Subset>)> {
-    let parts: Vec<&str> = line.split(':').collect();
-    if parts.len() == 2 {
-        if let Ok(id) = parts[0]
-            .trim()
-            .split_whitespace()
-            .last()
-            .unwrap()
-            .parse::<usize>()
-        {
-            let subsets = parts[1]
-                .split(';')
-                .flat_map(|subset_str| parse_subset(subset_str))
-                .collect();
-            return Some((id, subsets));
-        }
-    }
-    None
+fn parse_game_line(
+    line_number: usize,
+    line: &str,
+) -> AocResult<(usize, Vec<Subset>)> {
+    let (game, subsets) = line.split_once(':').ok_or_else(|| {
+        AocError::parse(line_number, 1, "expected `Game <id>: <subsets>`")
+    })?;
+    let id = game
+        .split_whitespace()
+        .last()
+        .ok_or_else(|| AocError::parse(line_number, 1, "missing game id"))?;
+    let id = parse_token(line_number, line, id)?;
+
+    let subsets = subsets
+        .split(';')
+        .map(|subset_str| parse_subset(line_number, line, subset_str))
+        .collect::<AocResult<_>>()?;
+
+    Ok((id, subsets))
 }
 
-fn parse_subset(subset_str: &str) -> Option<Subset> {
+fn parse_subset(
+    line_number: usize,
+    line: &str,
+    subset_str: &str,
+) -> AocResult<Subset> {
     let mut counts = HashMap::new();
-    let parts: Vec<&str> = subset_str.split(',').map(|s| s.trim()).collect();
 
-    for part in parts.iter() {
-        let p: Vec<&str> = part.split_whitespace().collect();
-        if let Ok(count) = p[0].parse::<usize>() {
-            let color = p[1..].join(" ").to_lowercase();
-            counts.insert(color, count);
-        }
+    for part in subset_str.split(',').map(|s| s.trim()) {
+        let mut p = part.split_whitespace();
+        let (Some(count), Some(color)) = (p.next(), p.next()) else {
+            return Err(AocError::parse(
+                line_number,
+                column_of(line, part),
+                format!("expected `<count> <color>`, got `{}`", part),
+            ));
+        };
+
+        let count = parse_token(line_number, line, count)?;
+        let color = std::iter::once(color)
+            .chain(p)
+            .collect::<Vec<_>>()
+            .join(" ")
+            .to_lowercase();
+        counts.insert(color, count);
     }
-    Some(Subset { counts })
+
+    Ok(Subset { counts })
 }
 
 impl Game {
@@ -113,7 +140,7 @@ pub fn calculate_power_max_set(games: &[Game]) -> usize {
     for game in games {
         let mut product = 1;
         let max_set = game.calculate_max_set().unwrap();
-        for (_key, value) in max_set.counts.iter() {
+        for value in max_set.counts.values() {
             product *= value;
         }
         total_max_set += product;
@@ -121,3 +148,34 @@ pub fn calculate_power_max_set(games: &[Game]) -> usize {
 
     total_max_set
 }
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Input = Vec<Game>;
+
+    fn parse(&self, input: &str) -> AocResult<Self::Input> {
+        read_games_from_str(input)
+    }
+
+    fn part1(&self, games: &Self::Input) -> AocResult<Answer> {
+        let cube_counts: CubeCounts = [
+            ("red".to_string(), 12),
+            ("green".to_string(), 13),
+            ("blue".to_string(), 14),
+        ]
+        .into_iter()
+        .collect();
+
+        Ok(games
+            .iter()
+            .filter(|&game| is_game_possible(game, &cube_counts))
+            .map(|game| game.id)
+            .sum::<usize>()
+            .into())
+    }
+
+    fn part2(&self, games: &Self::Input) -> AocResult<Answer> {
+        Ok(calculate_power_max_set(games).into())
+    }
+}
@@ -1,41 +1,32 @@
+use crate::error::{parse_token, AocResult};
+use crate::solution::{Answer, Solution};
+use crate::utils::grid::Grid;
 use std::collections::hash_map::DefaultHasher;
 use std::collections::HashMap;
 use std::fs::File;
 use std::hash::{Hash, Hasher};
 use std::io::{self, BufRead};
 
-fn split_string_to_numbers(input: &str) -> Vec<(usize, usize, i32)> {
+pub type PartNumbers = Vec<Vec<(usize, usize, i32)>>;
+
+fn split_string_to_numbers(
+    line_number: usize,
+    input: &str,
+) -> AocResult<Vec<(usize, usize, i32)>> {
+    let mut numbers = Vec::new();
     let mut start_idx = None;
-    let mut end_idx = None;
 
-    input
-        .chars()
-        .enumerate()
-        .filter_map(|(idx, c)| {
-            if c.is_numeric() {
-                if start_idx.is_none() {
-                    start_idx = Some(idx);
-                }
-                end_idx = Some(idx);
-                if idx == input.len() - 1 {
-                    return Some((
-                        start_idx.unwrap(),
-                        end_idx.unwrap(),
-                        input[start_idx.unwrap()..=end_idx.unwrap()]
-                            .parse::<i32>()
-                            .unwrap(),
-                    ));
-                }
-                None
-            } else if let (Some(start), Some(end)) =
-                (start_idx.take(), end_idx.take())
-            {
-                Some((start, end, input[start..=end].parse::<i32>().unwrap()))
-            } else {
-                None
-            }
-        })
-        .collect()
+    // A trailing sentinel closes a number that runs to the end of the line
+    for (idx, c) in input.char_indices().chain([(input.len(), '.')]) {
+        if c.is_ascii_digit() {
+            start_idx.get_or_insert(idx);
+        } else if let Some(start) = start_idx.take() {
+            let value = parse_token(line_number, input, &input[start..idx])?;
+            numbers.push((start, idx - 1, value));
+        }
+    }
+
+    Ok(numbers)
 }
 
 fn is_next_to_symbol(s: &char) -> bool {
@@ -43,70 +34,34 @@ fn is_next_to_symbol(s: &char) -> bool {
 }
 
 pub fn check_surroundings(
-    part_numbers: &Vec<Vec<(usize, usize, i32)>>,
-    lines: &Vec<String>,
+    part_numbers: &[Vec<(usize, usize, i32)>],
+    schematic: &Grid<char>,
 ) -> (Vec<i32>, HashMap<u64, Vec<i32>>) {
     let mut result_parts: Vec<i32> = Vec::new();
     let mut gears: HashMap<u64, Vec<i32>> = HashMap::new();
 
-    for (id, line) in lines.iter().enumerate() {
-        let part_number_by_row = &part_numbers[id];
-        let current_line: Vec<char> = line.chars().collect();
-
-        'this_loop: for part_number in part_number_by_row.iter() {
-            let (start, end, value) = part_number;
-            let prev_line: Option<Vec<char>> = if id > 0 {
-                Some(lines[id - 1].chars().collect())
-            } else {
-                None
-            };
-            let next_line: Option<Vec<char>> = if id < lines.len() - 1 {
-                Some(lines[id + 1].chars().collect())
-            } else {
-                None
-            };
-
-            // check left
-            if *start > 0 && is_next_to_symbol(&current_line[*start - 1]) {
-                update_map(&mut gears, id, *start - 1, *value);
-                result_parts.push(*value);
-                continue 'this_loop;
-            }
-
-            // check right
-            if *end < line.len() - 1
-                && is_next_to_symbol(&current_line[*end + 1])
-            {
-                update_map(&mut gears, id, *end + 1, *value);
-                result_parts.push(*value);
-                continue 'this_loop;
-            }
-
-            let start = if *start > 0 { *start - 1 } else { *start };
-            let end = if *end < line.len() - 1 {
-                *end + 1
-            } else {
-                *end
-            };
-
-            for i in start..=end {
-                // check top
-                if let Some(l) = &prev_line {
-                    if is_next_to_symbol(&l[i]) {
-                        update_map(&mut gears, id - 1, i, *value);
-                        result_parts.push(*value);
-                        continue 'this_loop;
-                    }
-                }
-
-                // check bottom
-                if let Some(l) = &next_line {
-                    if is_next_to_symbol(&l[i]) {
-                        update_map(&mut gears, id + 1, i, *value);
-                        result_parts.push(*value);
-                        continue 'this_loop;
-                    }
-                }
+    for (y, part_number_by_row) in part_numbers.iter().enumerate() {
+        for &(start, end, value) in part_number_by_row {
+            // left and right first, then the rows above and below, diagonals
+            // included
+            let left = start.checked_sub(1).map(|x| (x, y));
+            let right = Some((end + 1, y));
+            let above_and_below =
+                (start.saturating_sub(1)..=end + 1).flat_map(|x| {
+                    [y.checked_sub(1).map(|y| (x, y)), Some((x, y + 1))]
+                });
+
+            let symbol = [left, right]
+                .into_iter()
+                .chain(above_and_below)
+                .flatten()
+                .find(|&(x, y)| {
+                    schematic.get(x, y).is_some_and(is_next_to_symbol)
+                });
+
+            if let Some((x, y)) = symbol {
+                update_map(&mut gears, y, x, value);
+                result_parts.push(value);
             }
         }
     }
@@ -114,11 +69,11 @@ pub fn check_surroundings(
     (result_parts, gears)
 }
 
-pub fn calculate_sum(numbers: &Vec<i32>) -> i32 {
+pub fn calculate_sum(numbers: &[i32]) -> i32 {
     numbers.iter().sum()
 }
 
-pub fn calculate_product(numbers: &Vec<i32>) -> i32 {
+pub fn calculate_product(numbers: &[i32]) -> i32 {
     numbers.iter().product()
 }
 
@@ -153,21 +108,59 @@ pub fn hash_matrix_index(row: usize, col: usize) -> u64 {
     hasher.finish()
 }
 
-pub fn process_file(
-    file_path: &str,
-) -> io::Result<(Vec<Vec<(usize, usize, i32)>>, Vec<String>)> {
+pub fn process_file(file_path: &str) -> AocResult<(PartNumbers, Grid<char>)> {
     let file = File::open(file_path)?;
-    let reader = io::BufReader::new(file);
+    process_reader(io::BufReader::new(file))
+}
 
-    let mut part_numbers = Vec::new();
-    let mut lines = Vec::new();
+pub fn process_str(input: &str) -> AocResult<(PartNumbers, Grid<char>)> {
+    process_reader(input.as_bytes())
+}
 
-    for line in reader.lines() {
-        let line = line?;
-        let numbers = split_string_to_numbers(&line);
-        part_numbers.push(numbers);
-        lines.push(line);
+pub fn process_reader(
+    reader: impl BufRead,
+) -> AocResult<(PartNumbers, Grid<char>)> {
+    let schematic = Grid::from_reader(reader, |c| {
+        if c.is_ascii() {
+            Ok(c)
+        } else {
+            Err("schematic must only contain ASCII characters")
+        }
+    })?;
+
+    let part_numbers = schematic
+        .rows()
+        .enumerate()
+        .map(|(y, row)| {
+            split_string_to_numbers(y + 1, &row.iter().collect::<String>())
+        })
+        .collect::<AocResult<PartNumbers>>()?;
+
+    Ok((part_numbers, schematic))
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Input = (PartNumbers, Grid<char>);
+
+    fn parse(&self, input: &str) -> AocResult<Self::Input> {
+        process_str(input)
     }
 
-    Ok((part_numbers, lines))
+    fn part1(
+        &self,
+        (part_numbers, schematic): &Self::Input,
+    ) -> AocResult<Answer> {
+        let (result_parts, _) = check_surroundings(part_numbers, schematic);
+        Ok(calculate_sum(&result_parts).into())
+    }
+
+    fn part2(
+        &self,
+        (part_numbers, schematic): &Self::Input,
+    ) -> AocResult<Answer> {
+        let (_, gears) = check_surroundings(part_numbers, schematic);
+        Ok(calculate_sum_of_gear_ratios(&gears).into())
+    }
 }
@@ -1,48 +1,62 @@
+use crate::error::{column_of, parse_token, AocError, AocResult};
+use crate::solution::{Answer, Solution};
 use std::collections::BTreeMap;
 use std::fs::File;
 use std::io::{self, BufRead};
 
-pub fn process_file(
-    path: &str,
-) -> Result<(Vec<Vec<u32>>, Vec<Vec<u32>>), io::Error> {
+pub type CardNumbers = (Vec<Vec<u32>>, Vec<Vec<u32>>);
+
+pub fn process_file(path: &str) -> AocResult<CardNumbers> {
     let file = File::open(path)?;
-    let reader = io::BufReader::new(file);
+    process_reader(io::BufReader::new(file))
+}
 
+pub fn process_str(input: &str) -> AocResult<CardNumbers> {
+    process_reader(input.as_bytes())
+}
+
+pub fn process_reader(reader: impl BufRead) -> AocResult<CardNumbers> {
     let mut winning_numbers = Vec::new();
     let mut having_numbers = Vec::new();
 
-    for line in reader.lines() {
+    for (index, line) in reader.lines().enumerate() {
         let line = line?;
-        let mut parts = line.split(':').map(|s| s.trim());
-
-        if let Some(second_part) = parts.nth(1) {
-            let mut sub_parts = second_part.split('|').map(|s| s.trim());
-
-            if let (Some(first_part), Some(second_part)) =
-                (sub_parts.next(), sub_parts.next())
-            {
-                let winning_number: Vec<u32> = first_part
-                    .split_whitespace()
-                    .map(|num_str| num_str.parse().unwrap_or(0))
-                    .collect();
-
-                let having_number: Vec<u32> = second_part
-                    .split_whitespace()
-                    .map(|num_str| num_str.parse().unwrap_or(0))
-                    .collect();
-
-                winning_numbers.push(winning_number);
-                having_numbers.push(having_number);
-            }
+        if line.trim().is_empty() {
+            continue;
         }
+
+        let (_, numbers) = line.split_once(':').ok_or_else(|| {
+            AocError::parse(index + 1, 1, "expected `Card <id>: <numbers>`")
+        })?;
+        let (first_part, second_part) =
+            numbers.split_once('|').ok_or_else(|| {
+                AocError::parse(
+                    index + 1,
+                    column_of(&line, numbers),
+                    "expected `|` between winning and having numbers",
+                )
+            })?;
+
+        let winning_number: Vec<u32> = first_part
+            .split_whitespace()
+            .map(|num_str| parse_token(index + 1, &line, num_str))
+            .collect::<AocResult<_>>()?;
+
+        let having_number: Vec<u32> = second_part
+            .split_whitespace()
+            .map(|num_str| parse_token(index + 1, &line, num_str))
+            .collect::<AocResult<_>>()?;
+
+        winning_numbers.push(winning_number);
+        having_numbers.push(having_number);
     }
 
     Ok((winning_numbers, having_numbers))
 }
 
 pub fn find_matching_card(
-    winning_numbers: &Vec<Vec<u32>>,
-    having_numbers: &Vec<Vec<u32>>,
+    winning_numbers: &[Vec<u32>],
+    having_numbers: &[Vec<u32>],
 ) -> (i32, u32) {
     let mut points = 0;
     let mut scratch_cards_map: BTreeMap<usize, u32> = winning_numbers
@@ -55,7 +69,7 @@ pub fn find_matching_card(
         let winning_number_row = &winning_numbers[row_id];
         let mut count: usize = 0;
         for hn in having_number_row.iter() {
-            if winning_number_row.contains(&hn) {
+            if winning_number_row.contains(hn) {
                 count += 1;
             }
         }
@@ -74,7 +88,7 @@ pub fn find_matching_card(
                     update_map(
                         &mut scratch_cards_map,
                         *key,
-                        current_row_value * 1,
+                        *current_row_value,
                     );
                 }
             }
@@ -93,3 +107,30 @@ pub fn find_matching_card(
 fn update_map(map: &mut BTreeMap<usize, u32>, key: usize, value: u32) {
     map.entry(key).and_modify(|v| *v += value);
 }
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Input = CardNumbers;
+
+    fn parse(&self, input: &str) -> AocResult<Self::Input> {
+        process_str(input)
+    }
+
+    fn part1(
+        &self,
+        (winning_numbers, having_numbers): &Self::Input,
+    ) -> AocResult<Answer> {
+        let (points, _) = find_matching_card(winning_numbers, having_numbers);
+        Ok(points.into())
+    }
+
+    fn part2(
+        &self,
+        (winning_numbers, having_numbers): &Self::Input,
+    ) -> AocResult<Answer> {
+        let (_, total_scratch_cards) =
+            find_matching_card(winning_numbers, having_numbers);
+        Ok(total_scratch_cards.into())
+    }
+}
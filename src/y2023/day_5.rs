@@ -1,33 +1,223 @@
-use rayon::prelude::*;
+use crate::error::{column_of, parse_token, AocError, AocResult};
+use crate::solution::{Answer, Solution};
+use crate::utils::interval::{Interval, IntervalSet};
 use std::fs::File;
 use std::io::{self, BufRead};
 
+/// One `<source>-to-<destination> map:` section, as source ranges paired with
+/// the start of the range they map to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CategoryMap {
+    pub source: String,
+    pub destination: String,
+    pub rules: Vec<(Interval<u128>, u128)>,
+}
+
+impl CategoryMap {
+    /// Values outside of every rule map to themselves. `None` if the mapped
+    /// value does not fit in a `u128`.
+    pub fn map_value(&self, value: u128) -> Option<u128> {
+        match self.rules.iter().find(|(source, _)| source.contains(value)) {
+            Some(&(source, destination)) => {
+                destination.checked_add(value - source.start)
+            }
+            None => Some(value),
+        }
+    }
+
+    pub fn map_ranges(&self, ranges: &IntervalSet<u128>) -> IntervalSet<u128> {
+        ranges.map_offsets(self.rules.iter().copied())
+    }
+
+    /// Every value that maps into `ranges`. A value covered by several rules
+    /// is mapped by the first, so later rules only claim what is left.
+    pub fn preimage(&self, ranges: &IntervalSet<u128>) -> IntervalSet<u128> {
+        let sources: IntervalSet<u128> =
+            self.rules.iter().map(|&(source, _)| source).collect();
+        let mut claimed = IntervalSet::new();
+        // Values outside of every rule map to themselves
+        let mut preimage = ranges.subtract(&sources);
+
+        for &(source, destination) in &self.rules {
+            let targets = IntervalSet::from_iter([Interval::from_len(
+                destination,
+                source.end - source.start,
+            )]);
+            let mapped_from: IntervalSet<u128> = ranges
+                .intersect(&targets)
+                .intervals()
+                .iter()
+                .map(|interval| interval.map_offset(destination, source.start))
+                .collect();
+
+            preimage = preimage.union(&mapped_from.subtract(&claimed));
+            claimed.insert(source);
+        }
+
+        preimage
+    }
+}
+
+/// Maps whose destination is the source of the next one, so that values can
+/// be followed from any category to any other. Going back down the chain
+/// follows the maps in reverse, through the values that map onto the given
+/// ones.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MapChain {
+    maps: Vec<CategoryMap>,
+}
+
+impl MapChain {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn maps(&self) -> &[CategoryMap] {
+        &self.maps
+    }
+
+    /// Every category in chain order.
+    pub fn categories(&self) -> impl Iterator<Item = &str> + '_ {
+        self.maps
+            .first()
+            .map(|map| map.source.as_str())
+            .into_iter()
+            .chain(self.maps.iter().map(|map| map.destination.as_str()))
+    }
+
+    /// Appends `map`, which must continue from the last destination and must
+    /// not lead back to a category already in the chain.
+    pub fn push(&mut self, map: CategoryMap) -> Result<(), String> {
+        if let Some(last) = self.maps.last() {
+            if map.source != last.destination {
+                return Err(format!(
+                    "`{}-to-{}` does not continue from `{}`",
+                    map.source, map.destination, last.destination
+                ));
+            }
+        }
+        if self
+            .categories()
+            .any(|category| category == map.destination)
+        {
+            return Err(format!(
+                "category `{}` appears twice in the chain",
+                map.destination
+            ));
+        }
+
+        self.maps.push(map);
+        Ok(())
+    }
+
+    /// Maps `value` from one category to another. Going back down the chain
+    /// gives the lowest value that maps onto `value`, if there is one.
+    pub fn map_value(
+        &self,
+        from: &str,
+        to: &str,
+        value: u128,
+    ) -> AocResult<u128> {
+        let (stages, forward) = self.stages(from, to)?;
+
+        let unsolvable = || {
+            AocError::unsolvable(format!(
+                "`{}` {} cannot be mapped to a `{}`",
+                from, value, to
+            ))
+        };
+
+        if forward {
+            return stages
+                .iter()
+                .try_fold(value, |value, map| map.map_value(value))
+                .ok_or_else(unsolvable);
+        }
+
+        let only_value =
+            Interval::checked_from_len(value, 1).ok_or_else(unsolvable)?;
+        let values = IntervalSet::from_iter([only_value]);
+        self.map_ranges(from, to, values)?.min().ok_or_else(|| {
+            AocError::unsolvable(format!(
+                "no `{}` maps onto `{}` {}",
+                to, from, value
+            ))
+        })
+    }
+
+    /// Maps `ranges` from one category to another. Going back down the chain
+    /// gives every value that maps into `ranges`.
+    pub fn map_ranges(
+        &self,
+        from: &str,
+        to: &str,
+        ranges: IntervalSet<u128>,
+    ) -> AocResult<IntervalSet<u128>> {
+        let (stages, forward) = self.stages(from, to)?;
+
+        Ok(if forward {
+            stages
+                .iter()
+                .fold(ranges, |ranges, map| map.map_ranges(&ranges))
+        } else {
+            stages
+                .iter()
+                .rev()
+                .fold(ranges, |ranges, map| map.preimage(&ranges))
+        })
+    }
+
+    /// The maps between `from` and `to` in chain order, and whether `from`
+    /// comes first. Empty when both are the same.
+    fn stages(
+        &self,
+        from: &str,
+        to: &str,
+    ) -> AocResult<(&[CategoryMap], bool)> {
+        let position = |name: &str| {
+            self.categories()
+                .position(|category| category == name)
+                .ok_or_else(|| {
+                    AocError::unsolvable(format!("unknown category `{}`", name))
+                })
+        };
+        let (start, end) = (position(from)?, position(to)?);
+
+        Ok(if start <= end {
+            (&self.maps[start..end], true)
+        } else {
+            (&self.maps[end..start], false)
+        })
+    }
+}
+
+#[derive(Debug)]
 pub struct MapData {
     pub seeds: Vec<u128>,
-    pub seed_to_soil: Vec<(u128, u128, u128)>,
-    pub soil_to_fertilizer: Vec<(u128, u128, u128)>,
-    pub fertilizer_to_water: Vec<(u128, u128, u128)>,
-    pub water_to_light: Vec<(u128, u128, u128)>,
-    pub light_to_temperature: Vec<(u128, u128, u128)>,
-    pub temperature_to_humidity: Vec<(u128, u128, u128)>,
-    pub humidity_to_location: Vec<(u128, u128, u128)>,
+    pub maps: MapChain,
 }
 
-pub fn process_file(file_path: &str) -> io::Result<MapData> {
+pub fn process_file(file_path: &str) -> AocResult<MapData> {
     let file = File::open(file_path)?;
-    let reader = io::BufReader::new(file);
+    process_reader(io::BufReader::new(file))
+}
+
+pub fn process_str(input: &str) -> AocResult<MapData> {
+    process_reader(input.as_bytes())
+}
 
+pub fn process_reader(reader: impl BufRead) -> AocResult<MapData> {
     let mut sections = vec![];
     let mut current_section = Vec::new();
 
-    for line in reader.lines() {
+    for (index, line) in reader.lines().enumerate() {
         let line = line?;
 
         if line.trim().is_empty() {
             // If the line is empty, start a new section
             sections.push(std::mem::take(&mut current_section));
         } else {
-            current_section.push(line);
+            current_section.push((index + 1, line));
         }
     }
 
@@ -35,183 +225,157 @@ pub fn process_file(file_path: &str) -> io::Result<MapData> {
     sections.push(current_section);
 
     let mut seeds = Vec::new();
-    let mut seed_to_soil = Vec::new();
-    let mut soil_to_fertilizer = Vec::new();
-    let mut fertilizer_to_water = Vec::new();
-    let mut water_to_light = Vec::new();
-    let mut light_to_temperature = Vec::new();
-    let mut temperature_to_humidity = Vec::new();
-    let mut humidity_to_location = Vec::new();
+    let mut maps = MapChain::new();
 
     for section in sections {
-        let mut lines = section.into_iter().filter(|s| !s.trim().is_empty());
-
-        if let Some(header) = lines.next() {
-            let mut parts = header.split(':').map(|s| s.trim());
-
-            match parts.next() {
-                Some("seeds") => seeds.extend(
-                    parts
-                        .next()
-                        .unwrap_or_default()
-                        .split_whitespace()
-                        .filter_map(|s| s.parse::<u128>().ok()),
-                ),
-                Some("seed-to-soil map") => {
-                    seed_to_soil.extend(parse_tuple_lines(&mut lines))
-                }
-                Some("soil-to-fertilizer map") => {
-                    soil_to_fertilizer.extend(parse_tuple_lines(&mut lines))
-                }
-                Some("fertilizer-to-water map") => {
-                    fertilizer_to_water.extend(parse_tuple_lines(&mut lines))
-                }
-                Some("water-to-light map") => {
-                    water_to_light.extend(parse_tuple_lines(&mut lines))
-                }
-                Some("light-to-temperature map") => {
-                    light_to_temperature.extend(parse_tuple_lines(&mut lines))
+        let mut lines = section.iter();
+
+        if let Some((line_number, header)) = lines.next() {
+            let (name, values) = header.split_once(':').ok_or_else(|| {
+                AocError::parse(*line_number, 1, "expected a `name:` header")
+            })?;
+            let name = name.trim();
+
+            if name == "seeds" {
+                for value in values.split_whitespace() {
+                    seeds.push(parse_token(*line_number, header, value)?);
                 }
-                Some("temperature-to-humidity map") => temperature_to_humidity
-                    .extend(parse_tuple_lines(&mut lines)),
-                Some("humidity-to-location map") => {
-                    humidity_to_location.extend(parse_tuple_lines(&mut lines))
+                if let Some((line_number, _)) = lines.next() {
+                    return Err(AocError::parse(
+                        *line_number,
+                        1,
+                        "expected a blank line after the seeds",
+                    ));
                 }
-                _ => {}
+                continue;
             }
+
+            let (source, destination) = name
+                .strip_suffix(" map")
+                .and_then(|categories| categories.split_once("-to-"))
+                .ok_or_else(|| {
+                    AocError::parse(
+                        *line_number,
+                        1,
+                        format!("unknown section `{}`", name),
+                    )
+                })?;
+
+            maps.push(CategoryMap {
+                source: source.to_string(),
+                destination: destination.to_string(),
+                rules: parse_rule_lines(&mut lines)?,
+            })
+            .map_err(|message| {
+                AocError::parse(*line_number, column_of(header, name), message)
+            })?;
         }
     }
 
-    Ok(MapData {
-        seeds,
-        seed_to_soil,
-        soil_to_fertilizer,
-        fertilizer_to_water,
-        water_to_light,
-        light_to_temperature,
-        temperature_to_humidity,
-        humidity_to_location,
-    })
+    Ok(MapData { seeds, maps })
 }
 
-pub fn find_lowest_location(map_data: &MapData) -> (u128, u128) {
-    let MapData {
-        seeds,
-        seed_to_soil,
-        soil_to_fertilizer,
-        fertilizer_to_water,
-        water_to_light,
-        light_to_temperature,
-        temperature_to_humidity,
-        humidity_to_location,
-    } = map_data;
-    let mut current_location;
-
-    let transform_chain = [
-        seed_to_soil,
-        soil_to_fertilizer,
-        fertilizer_to_water,
-        water_to_light,
-        light_to_temperature,
-        temperature_to_humidity,
-        humidity_to_location,
-    ];
-
-    let mut min_location = u128::MAX;
-
-    for &seed in seeds.iter() {
-        current_location = seed;
-
-        for transform_rule in transform_chain.iter() {
-            let mut temp = 0;
-            let mut is_within_range = false;
-            for &(dest_start, src_start, range_len) in transform_rule.iter() {
-                if src_start <= current_location
-                    && current_location < src_start + range_len
-                {
-                    is_within_range = true;
-                    temp = current_location - src_start + dest_start;
-                    break;
-                }
-            }
-            if is_within_range {
-                current_location = temp;
-            }
-        }
+pub fn find_lowest_location(map_data: &MapData) -> AocResult<(u128, u128)> {
+    Ok((
+        find_lowest_seed_location(map_data)?,
+        find_lowest_seed_range_location(map_data)?,
+    ))
+}
 
-        if current_location < min_location {
-            min_location = current_location;
-        }
+pub fn find_lowest_seed_location(map_data: &MapData) -> AocResult<u128> {
+    let locations = map_data
+        .seeds
+        .iter()
+        .map(|&seed| map_data.maps.map_value("seed", "location", seed))
+        .collect::<AocResult<Vec<u128>>>()?;
+
+    locations
+        .into_iter()
+        .min()
+        .ok_or_else(|| AocError::unsolvable("there are no seeds"))
+}
+
+/// Pushes the seed ranges through every stage as whole intervals, splitting
+/// them wherever a stage maps their parts differently.
+pub fn find_lowest_seed_range_location(map_data: &MapData) -> AocResult<u128> {
+    if map_data.seeds.len() % 2 == 1 {
+        return Err(AocError::unsolvable(
+            "the seeds do not pair up into `start length` ranges",
+        ));
     }
 
-    let min_locations: Vec<u128> = seeds
-        .par_chunks(2)
+    let seeds = map_data
+        .seeds
+        .chunks_exact(2)
         .map(|chunk| {
-            if let [seed_start, seed_range] = chunk {
-                let seed_start = *seed_start;
-                let seed_range = *seed_range;
-
-                (0..seed_range)
-                    .map(|i| {
-                        let mut current_location = seed_start + i;
-
-                        for transform_rule in transform_chain.iter() {
-                            let mut temp = 0;
-                            let mut is_within_range = false;
-                            for &(dest_start, src_start, range_len) in
-                                transform_rule.iter()
-                            {
-                                if src_start <= current_location
-                                    && current_location < src_start + range_len
-                                {
-                                    is_within_range = true;
-                                    temp = current_location - src_start
-                                        + dest_start;
-                                    break;
-                                }
-                            }
-                            if is_within_range {
-                                current_location = temp;
-                            }
-                        }
-
-                        current_location
-                    })
-                    .min()
-                    .unwrap_or(u128::MAX)
-            } else {
-                u128::MAX
-            }
+            Interval::checked_from_len(chunk[0], chunk[1]).ok_or_else(|| {
+                AocError::unsolvable(format!(
+                    "the seed range `{} {}` ends past u128::MAX",
+                    chunk[0], chunk[1]
+                ))
+            })
         })
-        .collect();
-
-    let min_location_for_seed_pairs =
-        min_locations.into_par_iter().min().unwrap_or(u128::MAX);
+        .collect::<AocResult<IntervalSet<u128>>>()?;
 
-    (min_location, min_location_for_seed_pairs)
+    map_data
+        .maps
+        .map_ranges("seed", "location", seeds)?
+        .min()
+        .ok_or_else(|| AocError::unsolvable("there are no seeds"))
 }
 
-fn parse_tuple_lines<I, S>(lines: I) -> Vec<(u128, u128, u128)>
+fn parse_rule_lines<'a, I>(lines: I) -> AocResult<Vec<(Interval<u128>, u128)>>
 where
-    I: Iterator<Item = S>,
-    S: AsRef<str>,
+    I: Iterator<Item = &'a (usize, String)>,
 {
     lines
-        .flat_map(|line| {
-            let mut parts = line.as_ref().split_whitespace();
-            let first = parts
-                .next()
-                .and_then(|s| s.parse::<u128>().ok())
-                .unwrap_or_default();
-            let second = parts
-                .next()
-                .and_then(|s| s.parse::<u128>().ok())
-                .unwrap_or_default();
-            let third = parts
-                .next()
-                .and_then(|s| s.parse::<u128>().ok())
-                .unwrap_or_default();
-            Some((first, second, third))
+        .map(|(line_number, line)| {
+            let tokens: Vec<&str> = line.split_whitespace().collect();
+            let values = tokens
+                .iter()
+                .map(|value| parse_token(*line_number, line, value))
+                .collect::<AocResult<Vec<u128>>>()?;
+
+            match values[..] {
+                [destination, source, length] => {
+                    let ends_past_max = |index: usize| {
+                        AocError::parse(
+                            *line_number,
+                            column_of(line, tokens[index]),
+                            "the range ends past u128::MAX",
+                        )
+                    };
+                    Interval::checked_from_len(destination, length)
+                        .ok_or_else(|| ends_past_max(0))?;
+                    let source = Interval::checked_from_len(source, length)
+                        .ok_or_else(|| ends_past_max(1))?;
+
+                    Ok((source, destination))
+                }
+                _ => Err(AocError::parse(
+                    *line_number,
+                    1,
+                    format!("expected 3 numbers, found {}", values.len()),
+                )),
+            }
         })
         .collect()
 }
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Input = MapData;
+
+    fn parse(&self, input: &str) -> AocResult<Self::Input> {
+        process_str(input)
+    }
+
+    fn part1(&self, map_data: &Self::Input) -> AocResult<Answer> {
+        Ok(find_lowest_seed_location(map_data)?.into())
+    }
+
+    fn part2(&self, map_data: &Self::Input) -> AocResult<Answer> {
+        Ok(find_lowest_seed_range_location(map_data)?.into())
+    }
+}
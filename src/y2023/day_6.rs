@@ -1,99 +1,204 @@
+use crate::error::{parse_token, AocError, AocResult};
+use crate::solution::{Answer, Solution};
+use crate::utils::math::isqrt;
 use std::fs::File;
 use std::io::{self, BufRead};
 
-pub fn process_file(
-    file_path: &str,
-) -> Result<(Vec<u128>, Vec<u128>), io::Error> {
+pub fn process_file(file_path: &str) -> AocResult<(Vec<u128>, Vec<u128>)> {
     let file = File::open(file_path)?;
-    let reader = io::BufReader::new(file);
+    process_reader(io::BufReader::new(file))
+}
+
+pub fn process_str(input: &str) -> AocResult<(Vec<u128>, Vec<u128>)> {
+    process_reader(input.as_bytes())
+}
 
+pub fn process_reader(
+    reader: impl BufRead,
+) -> AocResult<(Vec<u128>, Vec<u128>)> {
     let mut time_vector = Vec::new();
     let mut distance_vector = Vec::new();
 
-    for line in reader.lines() {
+    for (index, line) in reader.lines().enumerate() {
         let line = line?;
-        let parts: Vec<&str> = line.split(':').collect();
-
-        if parts.len() == 2 {
-            let values: Vec<u128> = parts[1]
-                .split_whitespace()
-                .filter_map(|s| s.parse().ok())
-                .collect();
-
-            match parts[0].trim() {
-                "Time" => time_vector.extend(values),
-                "Distance" => distance_vector.extend(values),
-                _ => eprintln!("Unknown category: {}", parts[0]),
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let (category, numbers) = line.split_once(':').ok_or_else(|| {
+            AocError::parse(index + 1, 1, "expected `<category>: <values>`")
+        })?;
+        let values = numbers
+            .split_whitespace()
+            .map(|value| parse_token(index + 1, &line, value))
+            .collect::<AocResult<Vec<u128>>>()?;
+
+        match category.trim() {
+            "Time" => time_vector.extend(values),
+            "Distance" => distance_vector.extend(values),
+            _ => {
+                return Err(AocError::parse(
+                    index + 1,
+                    1,
+                    format!("unknown category `{}`", category.trim()),
+                ))
             }
         }
     }
 
+    if time_vector.len() != distance_vector.len() {
+        return Err(AocError::parse(
+            1,
+            1,
+            format!(
+                "{} times but {} distances",
+                time_vector.len(),
+                distance_vector.len()
+            ),
+        ));
+    }
+
     Ok((time_vector, distance_vector))
 }
 
 pub fn count_number_of_ways_to_beat_record(
-    time_vector: &Vec<u128>,
-    distance_vector: &Vec<u128>,
-) -> (u128, u128) {
-    let mut counts = Vec::new();
+    time_vector: &[u128],
+    distance_vector: &[u128],
+) -> AocResult<(u128, u128)> {
+    Ok((
+        ways_to_beat_each_record(time_vector, distance_vector)?,
+        ways_to_beat_combined_record(time_vector, distance_vector)?,
+    ))
+}
 
-    for (id, &time) in time_vector.iter().enumerate() {
-        counts.push(calculate_count(time, distance_vector[id]));
-    }
+/// The product of the number of ways to win every race.
+pub fn ways_to_beat_each_record(
+    time_vector: &[u128],
+    distance_vector: &[u128],
+) -> AocResult<u128> {
+    let counts: Vec<u128> = time_vector
+        .iter()
+        .zip(distance_vector)
+        .map(|(&time, &distance)| calculate_count(time, distance))
+        .collect();
 
-    let combined_time: u128 = time_vector
+    calculate_product(&counts)
+}
+
+/// The number of ways to win once the kerning is fixed and all races are one
+/// long race.
+pub fn ways_to_beat_combined_record(
+    time_vector: &[u128],
+    distance_vector: &[u128],
+) -> AocResult<u128> {
+    let combined_time = concatenate(time_vector)?;
+    let combined_distance = concatenate(distance_vector)?;
+
+    Ok(calculate_count(combined_time, combined_distance))
+}
+
+/// Joins the decimal digits of `numbers` into one number.
+fn concatenate(numbers: &[u128]) -> AocResult<u128> {
+    numbers
         .iter()
-        .map(|&num| num.to_string())
-        .collect::<String>()
-        .parse()
-        .unwrap_or_default();
-    let combined_distance: u128 = distance_vector
+        .try_fold(0u128, |combined, &number| {
+            // Shift one digit at a time, since the power of ten one digit
+            // longer than `number` may not fit even when the result does
+            let mut combined = combined;
+            let mut digits = number;
+            loop {
+                combined = combined.checked_mul(10)?;
+                digits /= 10;
+                if digits == 0 {
+                    break;
+                }
+            }
+
+            combined.checked_add(number)
+        })
+        .ok_or_else(|| {
+            AocError::unsolvable("the combined race does not fit in a u128")
+        })
+}
+
+fn calculate_product(numbers: &[u128]) -> AocResult<u128> {
+    numbers
         .iter()
-        .map(|&num| num.to_string())
-        .collect::<String>()
-        .parse()
-        .unwrap_or_default();
-
-    (
-        calculate_product(&counts),
-        calculate_count(combined_time, combined_distance),
-    )
+        .try_fold(1u128, |product, &number| product.checked_mul(number))
+        .ok_or_else(|| {
+            AocError::unsolvable("the product of the counts overflows a u128")
+        })
 }
 
-fn calculate_product(numbers: &Vec<u128>) -> u128 {
-    numbers.iter().product()
+/// Whether holding the button for `hold` ms goes farther than `distance` in
+/// a race of `time` ms, that is `hold * (time - hold) > distance`, without
+/// computing the product.
+fn beats(time: u128, distance: u128, hold: u128) -> bool {
+    hold < time && hold > distance / (time - hold)
 }
 
+/// Counts the hold times `t` with `t * (time - t) > distance`. Those form the
+/// range between the roots `(time ± sqrt(time² - 4 * distance)) / 2`, which
+/// is symmetric around `time / 2`.
 fn calculate_count(time: u128, distance: u128) -> u128 {
-    let mut mid;
-    let mut count = 0;
-    if time % 2 != 0 {
-        mid = time / 2;
-        while mid > 0 {
-            if mid * (time - mid) > distance {
-                count += 2;
-            } else {
-                break;
-            }
+    let half = time / 2;
+    // The middle is the best hold time, so nothing wins if it does not
+    if !beats(time, distance, half) {
+        return 0;
+    }
 
-            mid -= 1;
-        }
-    } else {
-        mid = time / 2;
-        if mid * (time - mid) > distance {
-            count += 1;
-            mid -= 1;
-            while mid > 0 {
-                if mid * (time - mid) > distance {
-                    count += 2;
-                } else {
-                    break;
-                }
+    let mut first = match (time.checked_mul(time), distance.checked_mul(4)) {
+        // Winning in the middle means the discriminant is positive
+        (Some(square), Some(distance)) => (time - isqrt(square - distance)) / 2,
+        _ => first_win_by_bisection(time, distance),
+    };
 
-                mid -= 1;
-            }
+    // The rounded root can be one off either way
+    while first > 0 && beats(time, distance, first - 1) {
+        first -= 1;
+    }
+    while !beats(time, distance, first) {
+        first += 1;
+    }
+
+    time - 2 * first + 1
+}
+
+/// The first winning hold time, given that `time / 2` wins, for races too
+/// long to square.
+fn first_win_by_bisection(time: u128, distance: u128) -> u128 {
+    let (mut low, mut high) = (0, time / 2);
+    while low < high {
+        let middle = low + (high - low) / 2;
+        if beats(time, distance, middle) {
+            high = middle;
+        } else {
+            low = middle + 1;
         }
     }
+    low
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Input = (Vec<u128>, Vec<u128>);
+
+    fn parse(&self, input: &str) -> AocResult<Self::Input> {
+        process_str(input)
+    }
 
-    count
+    fn part1(
+        &self,
+        (time_vector, distance_vector): &Self::Input,
+    ) -> AocResult<Answer> {
+        Ok(ways_to_beat_each_record(time_vector, distance_vector)?.into())
+    }
+
+    fn part2(
+        &self,
+        (time_vector, distance_vector): &Self::Input,
+    ) -> AocResult<Answer> {
+        Ok(ways_to_beat_combined_record(time_vector, distance_vector)?.into())
+    }
 }
@@ -1,254 +1,255 @@
-use std::cmp::Ordering;
-use std::collections::{HashMap, HashSet};
+use crate::error::{column_of, parse_token, AocError, AocResult};
+use crate::solution::{Answer, Solution};
+use std::fmt;
 use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::io::{self, BufRead};
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+/// Hand types from weakest to strongest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum HandType {
-    FiveOfAKind,
-    FourOfAKind,
-    FullHouse,
-    ThreeOfAKind,
-    TwoPair,
-    OnePair,
     HighCard,
+    OnePair,
+    TwoPair,
+    ThreeOfAKind,
+    FullHouse,
+    FourOfAKind,
+    FiveOfAKind,
 }
 
 impl HandType {
-    pub fn strength(&self) -> usize {
-        match self {
-            HandType::FiveOfAKind => 7,
-            HandType::FourOfAKind => 6,
-            HandType::FullHouse => 5,
-            HandType::ThreeOfAKind => 4,
-            HandType::TwoPair => 3,
-            HandType::OnePair => 2,
-            HandType::HighCard => 1,
+    /// The type of a hand whose two largest groups of equal cards have
+    /// `largest` and `second` cards.
+    fn from_groups(largest: u8, second: u8) -> Self {
+        match (largest, second) {
+            (5, _) => HandType::FiveOfAKind,
+            (4, _) => HandType::FourOfAKind,
+            (3, 2) => HandType::FullHouse,
+            (3, _) => HandType::ThreeOfAKind,
+            (2, 2) => HandType::TwoPair,
+            (2, _) => HandType::OnePair,
+            _ => HandType::HighCard,
         }
     }
 }
 
-fn get_card_strength(card: char) -> usize {
-    match card {
-        'A' => 14,
-        'K' => 13,
-        'Q' => 12,
-        'J' => 11,
-        'T' => 10,
-        _ => card.to_digit(10).unwrap() as usize,
-    }
-}
+/// Card labels from weakest to strongest under the standard rules.
+pub const CARDS: &str = "23456789TJQKA";
 
-fn get_card_strength_with_joker(card: char) -> usize {
-    match card {
-        'A' => 14,
-        'K' => 13,
-        'Q' => 12,
-        'J' => 1,
-        'T' => 10,
-        _ => card.to_digit(10).unwrap() as usize,
-    }
-}
+const JACK: u8 = 9;
 
-fn compare_hands(hand1: &str, hand2: &str) -> Ordering {
-    let type1 = determine_hand_type(hand1);
-    let type2 = determine_hand_type(hand2);
-
-    let type1_strength = type1.strength();
-    let type2_strength = type2.strength();
-
-    if type1_strength != type2_strength {
-        return type1_strength.cmp(&type2_strength);
-    }
-
-    // Both hands have the same type; compare cards one by one
-    let cards1: Vec<_> = hand1.chars().collect();
-    let cards2: Vec<_> = hand2.chars().collect();
-
-    for (card1, card2) in cards1.iter().zip(cards2.iter()) {
-        let strength1 = get_card_strength(*card1);
-        let strength2 = get_card_strength(*card2);
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Rules {
+    Standard,
+    /// `J` cards are jokers: the weakest card on their own, but they join
+    /// the largest group of other cards when deciding the hand type.
+    Jokers,
+}
 
-        match strength1.cmp(&strength2) {
-            Ordering::Equal => continue,
-            other => return other,
+impl Rules {
+    /// Strength of the card at `index` in [`CARDS`], 0 being the weakest.
+    fn strength(self, index: u8) -> u8 {
+        match self {
+            Rules::Standard => index,
+            Rules::Jokers if index == JACK => 0,
+            Rules::Jokers => index + 1,
         }
     }
-
-    cards1.len().cmp(&cards2.len()).reverse() // Compare lengths in reverse order
 }
 
-fn generate_map_to_joker(hands: &Vec<String>) -> HashMap<String, String> {
-    let mut hand_map = HashMap::new();
+/// Five cards with their type and strengths worked out under a set of rules.
+/// Hands compare by type, then card by card, which is also how the derived
+/// ordering goes through the fields.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Hand {
+    hand_type: HandType,
+    strengths: [u8; 5],
+    rules: Rules,
+    cards: [u8; 5],
+}
 
-    for original_hand in hands {
-        let j_indices: HashSet<usize> = original_hand
-            .char_indices()
-            .filter(|&(_, c)| c == 'J')
-            .map(|(i, _)| i)
-            .collect();
+impl Hand {
+    /// Parses five card labels, or `None` if `cards` is anything else.
+    pub fn parse(cards: &str, rules: Rules) -> Option<Self> {
+        let mut indices = [0; 5];
+        let mut labels = cards.chars();
 
-        if j_indices.is_empty() {
-            // If there are no 'J' in the original hand, just return the original hand
-            hand_map.insert(original_hand.clone(), original_hand.clone());
-            continue;
+        for index in &mut indices {
+            *index = CARDS.find(labels.next()?)? as u8;
         }
+        if labels.next().is_some() {
+            return None;
+        }
+
+        Some(Hand::new(indices, rules))
+    }
 
-        let mut all_hands = vec![original_hand.clone()];
+    fn new(cards: [u8; 5], rules: Rules) -> Self {
+        let mut counts = [0u8; CARDS.len()];
+        for &card in &cards {
+            counts[card as usize] += 1;
+        }
 
-        for replacement in original_hand.chars().filter(|&c| c != 'J') {
-            let mut modified_hand_chars: Vec<char> =
-                original_hand.chars().collect();
-            for i in &j_indices {
-                modified_hand_chars[*i] = replacement;
+        let jokers = match rules {
+            Rules::Standard => 0,
+            Rules::Jokers => std::mem::take(&mut counts[JACK as usize]),
+        };
+        let (mut largest, mut second) = (0, 0);
+        for &count in &counts {
+            if count > largest {
+                (largest, second) = (count, largest);
+            } else if count > second {
+                second = count;
             }
-            let modified_hand: String = modified_hand_chars.iter().collect();
-            all_hands.push(modified_hand);
         }
 
-        // Find the hand with maximum strength among all hands
-        if let Some(max_strength_hand) = find_max_strength_hand(&all_hands) {
-            hand_map.insert(original_hand.clone(), max_strength_hand.clone());
+        Hand {
+            hand_type: HandType::from_groups(largest + jokers, second),
+            strengths: cards.map(|card| rules.strength(card)),
+            rules,
+            cards,
         }
     }
 
-    hand_map
-}
-
-fn compare_hands_with_joker(
-    hand1: &str,
-    joker_hand1: &str,
-    hand2: &str,
-    joker_hand2: &str,
-) -> Ordering {
-    let type1 = determine_hand_type(joker_hand1);
-    let type2 = determine_hand_type(joker_hand2);
-
-    let type1_strength = type1.strength();
-    let type2_strength = type2.strength();
-
-    if type1_strength != type2_strength {
-        return type1_strength.cmp(&type2_strength);
+    /// The same cards under other rules.
+    pub fn with_rules(&self, rules: Rules) -> Self {
+        Hand::new(self.cards, rules)
     }
 
-    // Both hands have the same type; compare cards one by one
-    let cards1: Vec<_> = hand1.chars().collect();
-    let cards2: Vec<_> = hand2.chars().collect();
+    pub fn hand_type(&self) -> HandType {
+        self.hand_type
+    }
 
-    for (card1, card2) in cards1.iter().zip(cards2.iter()) {
-        let strength1 = get_card_strength_with_joker(*card1);
-        let strength2 = get_card_strength_with_joker(*card2);
+    pub fn rules(&self) -> Rules {
+        self.rules
+    }
+}
 
-        match strength1.cmp(&strength2) {
-            Ordering::Equal => continue,
-            other => return other,
+impl fmt::Display for Hand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for &card in &self.cards {
+            write!(f, "{}", CARDS.as_bytes()[card as usize] as char)?;
         }
+
+        Ok(())
     }
+}
+
+/// A hand and its bid alongside the line they were read from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bid {
+    pub line: usize,
+    pub hand: Hand,
+    pub bid: u32,
+}
 
-    cards1.len().cmp(&cards2.len()).reverse() // Compare lengths in reverse order
+/// Total winnings with every hand ranked under `rules`. Equal hands are
+/// ranked in the order of their lines.
+pub fn total_winnings(bids: &[Bid], rules: Rules) -> u32 {
+    let mut hands: Vec<(Hand, usize, u32)> = bids
+        .iter()
+        .map(|bid| (bid.hand.with_rules(rules), bid.line, bid.bid))
+        .collect();
+    hands.sort_by_key(|&(hand, line, _)| (hand, line));
+
+    hands
+        .iter()
+        .zip(1..)
+        .map(|(&(_, _, bid), rank)| rank * bid)
+        .sum()
 }
 
-fn determine_hand_type(hand: &str) -> HandType {
-    let card_counts = count_cards(hand);
+pub fn calculate_total(bids: &[Bid]) -> (u32, u32) {
+    (
+        total_winnings(bids, Rules::Standard),
+        total_winnings(bids, Rules::Jokers),
+    )
+}
 
-    let distinct_labels = card_counts.len();
+pub fn process_file(file_path: &str) -> AocResult<Vec<Bid>> {
+    let file = File::open(file_path)?;
+    process_reader(io::BufReader::new(file))
+}
 
-    if card_counts.values().any(|&count| count == 5) {
-        HandType::FiveOfAKind
-    } else if card_counts.values().any(|&count| count == 4) {
-        HandType::FourOfAKind
-    } else if card_counts.values().any(|&count| count == 3)
-        && distinct_labels == 2
-    {
-        HandType::FullHouse
-    } else if card_counts.values().any(|&count| count == 3) {
-        HandType::ThreeOfAKind
-    } else if card_counts.values().filter(|&&count| count == 2).count() == 2
-        && distinct_labels == 3
-    {
-        HandType::TwoPair
-    } else if card_counts.values().any(|&count| count == 2)
-        && distinct_labels == 4
-    {
-        HandType::OnePair
-    } else {
-        HandType::HighCard
-    }
+pub fn process_str(input: &str) -> AocResult<Vec<Bid>> {
+    process_reader(input.as_bytes())
 }
 
-fn count_cards(hand: &str) -> HashMap<char, usize> {
-    let mut counts = HashMap::new();
+pub fn process_reader(reader: impl BufRead) -> AocResult<Vec<Bid>> {
+    let mut bids = Vec::new();
+
+    for (index, line) in reader.lines().enumerate() {
+        let line_content = line?;
+        if line_content.trim().is_empty() {
+            continue;
+        }
 
-    for card in hand.chars() {
-        *counts.entry(card).or_insert(0) += 1;
+        let mut parts = line_content.split_whitespace();
+
+        match (parts.next(), parts.next(), parts.next()) {
+            (Some(cards), Some(bid), None) => {
+                let hand = parse_hand(index + 1, &line_content, cards)?;
+                let bid = parse_token(index + 1, &line_content, bid)?;
+                bids.push(Bid {
+                    line: index + 1,
+                    hand,
+                    bid,
+                });
+            }
+            (_, _, Some(extra)) => {
+                return Err(AocError::parse(
+                    index + 1,
+                    column_of(&line_content, extra),
+                    "expected `<hand> <bid>`, found more",
+                ));
+            }
+            _ => {
+                return Err(AocError::parse(
+                    index + 1,
+                    line_content.len() + 1,
+                    "expected `<hand> <bid>`",
+                ));
+            }
+        }
     }
 
-    counts
+    Ok(bids)
 }
 
-pub fn calculate_total(result_map: &HashMap<String, u32>) -> (u32, u32) {
-    let mut hands: Vec<_> = result_map.keys().cloned().collect();
-    hands.sort_by(|a, b| compare_hands(a, b));
-
-    let mut total = 0;
-    for (index, hand) in hands.iter().enumerate() {
-        let bet = result_map.get(hand).unwrap();
-        total += (index as u32 + 1) * bet;
+fn parse_hand(line_number: usize, line: &str, cards: &str) -> AocResult<Hand> {
+    if let Some((offset, card)) = cards
+        .char_indices()
+        .find(|&(_, card)| !CARDS.contains(card))
+    {
+        return Err(AocError::parse(
+            line_number,
+            column_of(line, cards) + offset,
+            format!("unknown card `{}`, expected one of `{}`", card, CARDS),
+        ));
     }
 
-    let joker_map = generate_map_to_joker(&hands);
-    hands.sort_by(|a, b| {
-        compare_hands_with_joker(
-            a,
-            joker_map.get(a).unwrap(),
-            b,
-            joker_map.get(b).unwrap(),
+    Hand::parse(cards, Rules::Standard).ok_or_else(|| {
+        AocError::parse(
+            line_number,
+            column_of(line, cards),
+            format!("expected 5 cards, found {}", cards.len()),
         )
-    });
+    })
+}
 
-    let mut joker_total = 0;
-    for (index, hand) in hands.iter().enumerate() {
-        let bet = result_map.get(hand).unwrap();
-        joker_total += (index as u32 + 1) * bet;
-    }
+pub struct Day7;
 
-    (total, joker_total)
-}
+impl Solution for Day7 {
+    type Input = Vec<Bid>;
 
-fn find_max_strength_hand(hands: &Vec<String>) -> Option<String> {
-    hands.iter().max_by(|&a, &b| compare_hands(a, b)).cloned()
-}
+    fn parse(&self, input: &str) -> AocResult<Self::Input> {
+        process_str(input)
+    }
 
-pub fn process_file(file_path: &str) -> HashMap<String, u32> {
-    // Create a HashMap to store key-value pairs
-    let mut map = HashMap::new();
-
-    // Open the file and create a BufReader to efficiently read lines
-    if let Ok(file) = File::open(file_path) {
-        let reader = BufReader::new(file);
-
-        // Iterate over each line in the file
-        for line in reader.lines() {
-            if let Ok(line_content) = line {
-                // Split the line into key and value
-                let mut parts = line_content.split_whitespace();
-
-                if let (Some(key), Some(value)) = (parts.next(), parts.next()) {
-                    // Parse the value into u32
-                    if let Ok(parsed_value) = value.parse::<u32>() {
-                        // Insert key-value pair into the HashMap
-                        map.insert(key.to_string(), parsed_value);
-                    } else {
-                        eprintln!("Error parsing value as u32: {}", value);
-                    }
-                } else {
-                    eprintln!("Error parsing line: {}", line_content);
-                }
-            }
-        }
-    } else {
-        eprintln!("Error opening file: {}", file_path);
+    fn part1(&self, bids: &Self::Input) -> AocResult<Answer> {
+        Ok(total_winnings(bids, Rules::Standard).into())
     }
 
-    map
+    fn part2(&self, bids: &Self::Input) -> AocResult<Answer> {
+        Ok(total_winnings(bids, Rules::Jokers).into())
+    }
 }
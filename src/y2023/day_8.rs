@@ -1,216 +1,338 @@
-use std::collections::BTreeMap;
+use crate::error::{column_of, AocError, AocResult};
+use crate::solution::{Answer, Solution};
+use crate::utils::cycle::find_cycle_brent;
+use crate::utils::math::{checked_lcm, crt};
+use std::collections::HashMap;
 use std::fs::File;
 use std::io::{self, BufRead};
 
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Turn {
+    Left = 0,
+    Right = 1,
+}
+
+/// The network with every node label interned to a dense id, so that a step
+/// is a single array lookup.
+#[derive(Debug, Clone, Default)]
+pub struct Network {
+    names: Vec<String>,
+    ids: HashMap<String, usize>,
+    /// `[left, right]` for every id
+    nodes: Vec<[usize; 2]>,
+}
+
+impl Network {
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn id(&self, name: &str) -> Option<usize> {
+        self.ids.get(name).copied()
+    }
+
+    pub fn name(&self, id: usize) -> &str {
+        &self.names[id]
+    }
+
+    pub fn next(&self, id: usize, turn: Turn) -> usize {
+        self.nodes[id][turn as usize]
+    }
+
+    /// Every id whose label ends with `suffix`.
+    pub fn ids_ending_with(&self, suffix: char) -> Vec<usize> {
+        (0..self.len())
+            .filter(|&id| self.names[id].ends_with(suffix))
+            .collect()
+    }
+
+    fn intern(&mut self, name: &str) -> usize {
+        if let Some(id) = self.id(name) {
+            return id;
+        }
+
+        let id = self.names.len();
+        self.names.push(name.to_string());
+        self.ids.insert(name.to_string(), id);
+        self.nodes.push([id; 2]);
+        id
+    }
+}
+
+#[derive(Debug)]
 pub struct GraphAndInstructions {
-    pub graph: BTreeMap<String, Vec<String>>,
-    pub instructions: String,
+    pub network: Network,
+    pub instructions: Vec<Turn>,
 }
 
-pub fn process_file(
-    file_path: &str,
-) -> Result<GraphAndInstructions, io::Error> {
+pub fn process_file(file_path: &str) -> AocResult<GraphAndInstructions> {
     let file = File::open(file_path)?;
-    let reader = io::BufReader::new(file);
+    process_reader(io::BufReader::new(file))
+}
 
-    let mut graph: BTreeMap<String, Vec<String>> = BTreeMap::new();
-    let mut instructions = String::new();
+pub fn process_str(input: &str) -> AocResult<GraphAndInstructions> {
+    process_reader(input.as_bytes())
+}
+
+pub fn process_reader(reader: impl BufRead) -> AocResult<GraphAndInstructions> {
+    let mut network = Network::default();
+    let mut instructions = Vec::new();
+    let mut defined = Vec::new();
+    // Where every node was first mentioned, to report the ones never defined
+    let mut first_mentions = HashMap::new();
 
     for (index, line) in reader.lines().enumerate() {
         let line = line?;
         if index == 0 {
-            instructions = line.trim().to_string();
-        } else {
-            let parts: Vec<&str> = line.split('=').map(|s| s.trim()).collect();
+            instructions = parse_instructions(&line)?;
+        } else if !line.trim().is_empty() {
+            let (node, neighbors) = line.split_once('=').ok_or_else(|| {
+                AocError::parse(index + 1, 1, "expected `<node> = (<l>, <r>)`")
+            })?;
+            let neighbors: Vec<&str> = neighbors
+                .trim()
+                .trim_matches(|c| c == '(' || c == ')')
+                .split(',')
+                .map(|s| s.trim())
+                .collect();
+
+            let [left, right] = neighbors[..] else {
+                return Err(AocError::parse(
+                    index + 1,
+                    line.find('=').unwrap_or_default() + 2,
+                    format!("expected 2 neighbours, found {}", neighbors.len()),
+                ));
+            };
 
-            if parts.len() == 2 {
-                let node = parts[0].to_string();
-                let neighbors: Vec<String> = parts[1]
-                    .trim_matches(|c| c == '(' || c == ')')
-                    .split(',')
-                    .map(|s| s.trim().to_string())
-                    .collect();
+            let [id, left, right] = [node.trim(), left, right].map(|name| {
+                let id = network.intern(name);
+                first_mentions
+                    .entry(id)
+                    .or_insert((index + 1, column_of(&line, name)));
+                id
+            });
 
-                graph.insert(node, neighbors);
+            defined.resize(network.len(), false);
+            if std::mem::replace(&mut defined[id], true) {
+                return Err(AocError::parse(
+                    index + 1,
+                    column_of(&line, node.trim()),
+                    format!("node `{}` is defined twice", network.name(id)),
+                ));
             }
+            network.nodes[id] = [left, right];
         }
     }
 
+    defined.resize(network.len(), false);
+    if let Some(id) = (0..network.len()).find(|&id| !defined[id]) {
+        let (line, column) = first_mentions[&id];
+        return Err(AocError::parse(
+            line,
+            column,
+            format!("node `{}` is never defined", network.name(id)),
+        ));
+    }
+
     Ok(GraphAndInstructions {
-        graph,
+        network,
         instructions,
     })
 }
 
-fn contains(graph: &BTreeMap<String, Vec<String>>, s: impl ToString) -> bool {
-    let target = s.to_string();
-
-    for neighbors in graph.values() {
-        for node in neighbors {
-            if node.ends_with(&target) {
-                return true;
-            }
-        }
+fn parse_instructions(line: &str) -> AocResult<Vec<Turn>> {
+    let instructions = line.trim();
+    if instructions.is_empty() {
+        return Err(AocError::parse(1, 1, "expected at least one instruction"));
     }
-    false
+
+    instructions
+        .chars()
+        .enumerate()
+        .map(|(column, c)| match c {
+            'L' => Ok(Turn::Left),
+            'R' => Ok(Turn::Right),
+            _ => Err(AocError::parse(
+                1,
+                column_of(line, instructions) + column,
+                "instructions must only contain `L` and `R`",
+            )),
+        })
+        .collect()
 }
 
+/// Follows the instructions from AAA to ZZZ. The walk is a function of the
+/// node and the position in the instructions, so once such a state repeats
+/// without reaching ZZZ it never will.
 pub fn traverse_graph(
-    graph: &BTreeMap<String, Vec<String>>,
-    instructions: &str,
-) -> u64 {
-    if !contains(&graph, "ZZZ") {
-        return 0;
-    }
-
-    let start_node = graph.keys().next().map(|s| s.clone()).unwrap_or_default();
-    let mut current_node = start_node.to_string();
-    let mut steps = 0;
-
-    while !current_node.eq_ignore_ascii_case("ZZZ") {
-        for instruction in instructions.chars() {
-            match instruction {
-                'R' => {
-                    if let Some(neighbors) = graph.get(&current_node) {
-                        if let Some(right_node) = neighbors.get(1) {
-                            current_node = right_node.to_string();
-                            steps += 1;
-                        }
-                    }
-                }
-                'L' => {
-                    if let Some(neighbors) = graph.get(&current_node) {
-                        if let Some(left_node) = neighbors.get(0) {
-                            current_node = left_node.to_string();
-                            steps += 1;
-                        }
-                    }
-                }
-                _ => {
-                    // Ignore unknown instructions
-                }
-            }
-        }
-    }
+    network: &Network,
+    instructions: &[Turn],
+) -> AocResult<u64> {
+    let node = |name| {
+        network.id(name).ok_or_else(|| {
+            AocError::unsolvable(format!("the network has no node `{}`", name))
+        })
+    };
+    let (start, target) = (node("AAA")?, node("ZZZ")?);
 
-    steps
+    let mut is_end = vec![false; network.len()];
+    is_end[target] = true;
+
+    ghost_cycle(network, instructions, &is_end, start)
+        .hits
+        .first()
+        .copied()
+        .ok_or_else(|| {
+            AocError::unsolvable("following the instructions never reaches ZZZ")
+        })
 }
 
-fn find_start_nodes(graph: &BTreeMap<String, Vec<String>>) -> Vec<String> {
-    graph
-        .keys()
-        .filter(|&node| node.ends_with('A'))
-        .cloned()
-        .collect()
+/// The times at which a ghost stands on a `Z` node. Its walk is a function
+/// of the node and the position in the instructions, so from `offset` on it
+/// repeats every `period` steps.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GhostCycle {
+    pub offset: u64,
+    pub period: u64,
+    /// Every hit before `offset + period`, in order
+    pub hits: Vec<u64>,
 }
 
-fn traverse_graph_ends_with_z(
-    graph: &BTreeMap<String, Vec<String>>,
-    start_node: &str,
-    instructions: &str,
-) -> u64 {
-    if !contains(&graph, 'Z') {
-        return 0;
-    }
-
-    let mut current_node = start_node.to_string();
-    let mut steps = 0;
-
-    while !current_node.ends_with('Z') {
-        for instruction in instructions.chars() {
-            match instruction {
-                'R' => {
-                    if let Some(neighbors) = graph.get(&current_node) {
-                        if let Some(right_node) = neighbors.get(1) {
-                            current_node = right_node.to_string();
-                            steps += 1;
-                        }
-                    }
-                }
-                'L' => {
-                    if let Some(neighbors) = graph.get(&current_node) {
-                        if let Some(left_node) = neighbors.get(0) {
-                            current_node = left_node.to_string();
-                            steps += 1;
-                        }
-                    }
-                }
-                _ => {
-                    // Ignore unknown instructions
-                }
-            }
-        }
+impl GhostCycle {
+    pub fn is_hit(&self, time: u64) -> bool {
+        let time = if time < self.offset + self.period {
+            time
+        } else {
+            self.offset + (time - self.offset) % self.period
+        };
+
+        self.hits.binary_search(&time).is_ok()
     }
 
-    steps
+    /// Residues modulo the period of the hits that repeat.
+    fn residues(&self) -> impl Iterator<Item = u64> + '_ {
+        self.hits
+            .iter()
+            .filter(move |&&hit| hit >= self.offset)
+            .map(move |&hit| hit % self.period)
+    }
 }
 
-const fn gcd(mut a: u64, mut b: u64) -> u64 {
-    while b != 0 {
-        let temp = b;
-        b = a % b;
-        a = temp;
+pub fn ghost_cycle(
+    network: &Network,
+    instructions: &[Turn],
+    is_end: &[bool],
+    start: usize,
+) -> GhostCycle {
+    let step = |&(id, index): &(usize, usize)| {
+        (
+            network.next(id, instructions[index]),
+            (index + 1) % instructions.len(),
+        )
+    };
+    let (offset, period) = find_cycle_brent((start, 0), step);
+
+    let mut hits = Vec::new();
+    let mut state = (start, 0);
+    for time in 0..offset + period {
+        if is_end[state.0] {
+            hits.push(time as u64);
+        }
+        state = step(&state);
     }
-    a
-}
 
-const fn lcm(a: u64, b: u64) -> u64 {
-    if a == 0 || b == 0 {
-        return 0;
+    GhostCycle {
+        offset: offset as u64,
+        period: period as u64,
+        hits,
     }
-    (a * b) / gcd(a, b)
 }
 
-pub fn find_steps_lcm(
-    graph: &BTreeMap<String, Vec<String>>,
-    instructions: &str,
-) -> u64 {
-    let start_nodes = find_start_nodes(&graph);
-    let step_vector: Vec<u64> = start_nodes
-        .iter()
-        .map(|current_node| {
-            traverse_graph_ends_with_z(&graph, &current_node, &instructions)
-        })
+/// The first time at which every ghost stands on a `Z` node at once.
+pub fn find_ghost_steps(
+    network: &Network,
+    instructions: &[Turn],
+) -> AocResult<u64> {
+    let mut is_end = vec![false; network.len()];
+    for id in network.ids_ending_with('Z') {
+        is_end[id] = true;
+    }
+
+    let ghosts: Vec<GhostCycle> = network
+        .ids_ending_with('A')
+        .into_iter()
+        .map(|start| ghost_cycle(network, instructions, &is_end, start))
         .collect();
+    if ghosts.is_empty() {
+        return Err(AocError::unsolvable("no node ends with `A`"));
+    }
 
-    step_vector.clone().into_iter().reduce(lcm).unwrap()
-}
+    // Crafted inputs hit a `Z` exactly once per cycle, at multiples of it
+    if ghosts.iter().all(|ghost| {
+        ghost.offset <= ghost.period && ghost.hits == [ghost.period]
+    }) {
+        return ghosts
+            .iter()
+            .try_fold(1, |steps, ghost| checked_lcm(steps, ghost.period))
+            .ok_or_else(|| AocError::unsolvable("the step count overflows"));
+    }
 
-fn _traverse_graph_2(
-    graph: &BTreeMap<String, Vec<String>>,
-    instructions: &str,
-) -> u32 {
-    let mut current_nodes = find_start_nodes(&graph);
-    let mut steps = 0;
-
-    while !current_nodes.iter().all(|node| node.ends_with('Z')) {
-        for instruction in instructions.chars() {
-            let next_nodes: Vec<String> = current_nodes
-                .iter()
-                .flat_map(|current_node| {
-                    match instruction {
-                        'R' => graph
-                            .get(current_node)
-                            .and_then(|neighbors| neighbors.get(1)),
-                        'L' => graph
-                            .get(current_node)
-                            .and_then(|neighbors| neighbors.get(0)),
-                        _ => None,
-                    }
-                    .map(|node| node.to_string())
+    // Before every ghost is on its cycle, just try each time
+    let settled = ghosts.iter().map(|ghost| ghost.offset).max().unwrap_or(0);
+    if let Some(time) =
+        (0..settled).find(|&time| ghosts.iter().all(|g| g.is_hit(time)))
+    {
+        return Ok(time);
+    }
+
+    // Afterwards, combine every choice of hit of every ghost
+    let mut solutions = vec![(0u128, 1u128)];
+    for ghost in &ghosts {
+        let mut combined: Vec<(u128, u128)> = solutions
+            .iter()
+            .flat_map(|&solution| {
+                ghost.residues().filter_map(move |residue| {
+                    crt([solution, (residue as u128, ghost.period as u128)])
                 })
-                .collect();
+            })
+            .collect();
+        combined.sort_unstable();
+        combined.dedup();
+        solutions = combined;
+    }
 
-            if next_nodes.len() == current_nodes.len() {
-                println!("next_nodes: {:?}", &next_nodes);
-                current_nodes = next_nodes;
-                steps += 1;
-            } else {
-                // Break the loop if the condition is not met
-                break;
-            }
-        }
+    let settled = settled as u128;
+    solutions
+        .into_iter()
+        .map(|(residue, modulus)| {
+            residue
+                + settled.saturating_sub(residue).div_ceil(modulus) * modulus
+        })
+        .min()
+        .and_then(|time| u64::try_from(time).ok())
+        .ok_or_else(|| {
+            AocError::unsolvable("the ghosts never all stand on `Z` at once")
+        })
+}
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    type Input = GraphAndInstructions;
+
+    fn parse(&self, input: &str) -> AocResult<Self::Input> {
+        process_str(input)
+    }
+
+    fn part1(&self, data: &Self::Input) -> AocResult<Answer> {
+        Ok(traverse_graph(&data.network, &data.instructions)?.into())
     }
 
-    steps
+    fn part2(&self, data: &Self::Input) -> AocResult<Answer> {
+        Ok(find_ghost_steps(&data.network, &data.instructions)?.into())
+    }
 }
@@ -1,80 +1,114 @@
+use crate::error::{parse_token, AocError, AocResult};
+use crate::solution::{Answer, Solution};
+use crate::utils::math::extrapolate;
 use std::fs::File;
 use std::io::{self, BufRead};
 
-pub fn process_file(file_path: &str) -> (i32, i32) {
-    let mut start_sums: Vec<i32> = Vec::new();
-    let mut end_sums: Vec<i32> = Vec::new();
-
-    // Open the file and create a BufReader to efficiently read lines
-    if let Ok(file) = File::open(file_path) {
-        let reader = io::BufReader::new(file);
-
-        // Iterate over each line in the file
-        for line in reader.lines() {
-            if let Ok(line_content) = line {
-                let values: Vec<i32> = line_content
-                    .split_whitespace()
-                    .filter_map(|s| s.parse().ok())
-                    .collect();
-
-                let (start, end) = find_next_history_value(&values);
-                start_sums.push(start);
-                end_sums.push(end);
-            }
+pub fn process_file(file_path: &str) -> AocResult<(i32, i32)> {
+    let file = File::open(file_path)?;
+    process_reader(io::BufReader::new(file))
+}
+
+pub fn process_str(input: &str) -> AocResult<(i32, i32)> {
+    process_reader(input.as_bytes())
+}
+
+pub fn process_reader(reader: impl BufRead) -> AocResult<(i32, i32)> {
+    let histories = read_histories(reader)?;
+
+    Ok((
+        sum_extrapolated(&histories, Side::Before)?,
+        sum_extrapolated(&histories, Side::After)?,
+    ))
+}
+
+/// A history alongside the line it was read from.
+pub type History = (usize, Vec<i32>);
+
+pub fn read_histories(reader: impl BufRead) -> AocResult<Vec<History>> {
+    let mut histories = Vec::new();
+
+    for (index, line) in reader.lines().enumerate() {
+        let line_content = line?;
+        if line_content.trim().is_empty() {
+            continue;
         }
-    } else {
-        eprintln!("Error opening file: {}", file_path);
+
+        let values = line_content
+            .split_whitespace()
+            .map(|s| parse_token(index + 1, &line_content, s))
+            .collect::<AocResult<Vec<i32>>>()?;
+        histories.push((index + 1, values));
     }
 
-    (
-        start_sums.iter().fold(0, |acc, &x| acc + x),
-        end_sums.iter().fold(0, |acc, &x| acc + x),
-    )
+    Ok(histories)
 }
 
-fn find_next_history_value(values: &Vec<i32>) -> (i32, i32) {
-    let mut original_vector = values.clone();
-    let mut start_offsets: Vec<i32> = Vec::new();
-    let mut end_offsets: Vec<i32> = Vec::new();
-
-    let (first_element, last_element) = get_first_and_last(&original_vector);
-    start_offsets.push(first_element);
-    end_offsets.push(last_element);
-
-    loop {
-        let next_vector =
-            original_vector
-                .windows(2)
-                .fold(Vec::new(), |mut acc, window| {
-                    let difference = window[1] - window[0];
-                    acc.push(difference);
-                    acc
-                });
-
-        // Check if the next_vector contains all zeros
-        if next_vector.iter().all(|&diff| diff == 0) {
-            break;
-        }
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    Before,
+    After,
+}
+
+/// Sums up the value every history extrapolates to on the given side.
+pub fn sum_extrapolated(histories: &[History], side: Side) -> AocResult<i32> {
+    let mut sum = 0;
 
-        let (first_element, last_element) = get_first_and_last(&next_vector);
-        start_offsets.push(first_element);
-        end_offsets.push(last_element);
-        original_vector = next_vector;
+    for (line, values) in histories {
+        let value = extrapolate_side(values, side).ok_or_else(|| {
+            AocError::unsolvable(format!(
+                "the history on line {} overflows an i32",
+                line
+            ))
+        })?;
+        sum = i32::checked_add(sum, value).ok_or_else(|| {
+            AocError::unsolvable(format!(
+                "the sum up to line {} overflows an i32",
+                line
+            ))
+        })?;
     }
 
-    (
-        start_offsets.iter().rev().fold(0, |acc, &x| x - acc),
-        end_offsets.iter().fold(0, |acc, &x| acc + x),
-    )
+    Ok(sum)
 }
 
-fn get_first_and_last<T: Clone>(vector: &Vec<T>) -> (T, T) {
-    let Some(first_element) = vector.first().cloned() else {
-        panic!("can't get first element");
-    };
-    let Some(last_element) = vector.last().cloned() else {
-        panic!("can't get last element");
-    };
+/// The values just before and just after the history, or `None` if they
+/// overflow.
+pub fn find_next_history_value(values: &[i32]) -> Option<(i32, i32)> {
+    Some((
+        extrapolate_side(values, Side::Before)?,
+        extrapolate_side(values, Side::After)?,
+    ))
+}
+
+/// The value just past the history on one side, or `None` if it overflows.
+/// The other side is never computed, so it may overflow freely. An empty
+/// history has nothing to add to either end.
+pub fn extrapolate_side(values: &[i32], side: Side) -> Option<i32> {
+    if values.is_empty() {
+        return Some(0);
+    }
+
+    match side {
+        Side::Before => extrapolate(values, -1),
+        Side::After => extrapolate(values, i32::try_from(values.len()).ok()?),
+    }
+}
+
+pub struct Day9;
 
-    (first_element, last_element)
+impl Solution for Day9 {
+    type Input = Vec<History>;
+
+    fn parse(&self, input: &str) -> AocResult<Self::Input> {
+        read_histories(input.as_bytes())
+    }
+
+    fn part1(&self, histories: &Self::Input) -> AocResult<Answer> {
+        Ok(sum_extrapolated(histories, Side::After)?.into())
+    }
+
+    fn part2(&self, histories: &Self::Input) -> AocResult<Answer> {
+        Ok(sum_extrapolated(histories, Side::Before)?.into())
+    }
 }
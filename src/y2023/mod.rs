@@ -1,9 +1,12 @@
+use crate::solution::Registry;
+
 pub mod day_1;
 pub mod day_10;
 pub mod day_11;
 pub mod day_12;
 pub mod day_13;
 pub mod day_14;
+pub mod day_15;
 pub mod day_2;
 pub mod day_3;
 pub mod day_4;
@@ -12,3 +15,21 @@ pub mod day_6;
 pub mod day_7;
 pub mod day_8;
 pub mod day_9;
+
+pub fn register(registry: &mut Registry) {
+    registry.register(2023, 1, &day_1::Day1);
+    registry.register(2023, 2, &day_2::Day2);
+    registry.register(2023, 3, &day_3::Day3);
+    registry.register(2023, 4, &day_4::Day4);
+    registry.register(2023, 5, &day_5::Day5);
+    registry.register(2023, 6, &day_6::Day6);
+    registry.register(2023, 7, &day_7::Day7);
+    registry.register(2023, 8, &day_8::Day8);
+    registry.register(2023, 9, &day_9::Day9);
+    registry.register(2023, 10, &day_10::Day10);
+    registry.register(2023, 11, &day_11::Day11);
+    registry.register(2023, 12, &day_12::Day12);
+    registry.register(2023, 13, &day_13::Day13);
+    registry.register(2023, 14, &day_14::Day14);
+    registry.register(2023, 15, &day_15::Day15);
+}
@@ -0,0 +1,117 @@
+use std::path::{Path, PathBuf};
+
+use advent_of_code::answers::{Expected, Failure, Manifest};
+use advent_of_code::error::AocError;
+use advent_of_code::registry;
+use advent_of_code::solution::Answer;
+
+#[test]
+fn test_answers() {
+    let registry = registry();
+    let mut failures = Vec::new();
+
+    for year in registry.years() {
+        let path = format!("input/y{}/answers.toml", year);
+        let manifest = Manifest::load(year, Path::new(&path)).unwrap();
+        assert!(!manifest.entries.is_empty(), "{} has no entries", path);
+
+        for expected in &manifest.entries {
+            let check = manifest.check(&registry, expected);
+            if !check.passed() {
+                failures.push(check.to_string());
+            }
+        }
+    }
+
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}
+
+#[test]
+fn test_manifest_parse() {
+    let text = "\
+# comment
+[\"day_2_1.txt\"]  # trailing comment
+part1 = 8
+part2 = \"text\"
+
+[\"day_10_3.txt\"]
+part2 = 453
+";
+    let manifest = Manifest::parse(2023, PathBuf::from("input/y2023"), text);
+
+    assert_eq!(
+        manifest.unwrap().entries,
+        vec![
+            Expected {
+                file: "day_2_1.txt".to_string(),
+                day: 2,
+                part1: Some(Answer::Number(8)),
+                part2: Some(Answer::Text("text".to_string())),
+            },
+            Expected {
+                file: "day_10_3.txt".to_string(),
+                day: 10,
+                part1: None,
+                part2: Some(Answer::Number(453)),
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_manifest_parse_errors() {
+    let test_cases = [
+        ("part1 = 1\n", (1, 1)),
+        ("[\"day_1_1.txt\"]\npart3 = 1\n", (2, 1)),
+        ("[\"day_1_1.txt\"]\npart1 = abc\n", (2, 9)),
+        ("[\"input.txt\"]\n", (1, 3)),
+        ("[\"day_1_1.txt\"]\n[\"day_1_1.txt\"]\n", (2, 3)),
+    ];
+
+    for (text, expected) in test_cases {
+        match Manifest::parse(2023, PathBuf::new(), text) {
+            Err(AocError::Parse { line, column, .. }) => {
+                assert_eq!((line, column), expected, "{:?}", text);
+            }
+            other => {
+                panic!("expected a parse error for {:?}: {:?}", text, other)
+            }
+        }
+    }
+}
+
+#[test]
+fn test_check_reports_failures() {
+    let registry = registry();
+    let text = "\
+[\"day_9_1.txt\"]
+part1 = 114
+part2 = 3
+
+[\"day_9_404.txt\"]
+part1 = 1
+";
+    let manifest =
+        Manifest::parse(2023, PathBuf::from("input/y2023"), text).unwrap();
+
+    let mismatch = manifest.check(&registry, &manifest.entries[0]);
+    assert!(matches!(
+        mismatch.failures[..],
+        [Failure::Mismatch { part: 2, .. }]
+    ));
+
+    let missing = manifest.check(&registry, &manifest.entries[1]);
+    assert!(matches!(
+        missing.failures[..],
+        [Failure::Error(AocError::Io(_))]
+    ));
+}
+
+#[test]
+fn test_answer_from_number() {
+    assert_eq!(Answer::from(-7i64), Answer::Number(-7));
+    assert_eq!(Answer::from(u64::MAX), Answer::Number(u64::MAX.into()));
+    assert_eq!(Answer::from(42u128), Answer::Number(42));
+    assert_eq!(Answer::from(u128::MAX), Answer::Text(u128::MAX.to_string()));
+    assert_eq!(Answer::from(u128::MAX).to_string(), u128::MAX.to_string());
+}
@@ -0,0 +1,49 @@
+use std::fs;
+use std::time::Duration;
+
+use advent_of_code::bench::{bench, to_json, to_markdown, Timings};
+use advent_of_code::registry;
+
+#[test]
+fn test_timings_from_samples() {
+    let test_cases: [(&[u64], u64, u64, u64); 4] = [
+        (&[7], 7, 7, 7),
+        (&[40, 10, 30, 20], 10, 25, 40),
+        (&[50, 10, 40, 20, 30], 10, 30, 50),
+        (&[3; 20], 3, 3, 3),
+    ];
+
+    for (samples, min, median, p95) in test_cases {
+        let mut samples: Vec<Duration> = samples
+            .iter()
+            .map(|&us| Duration::from_micros(us))
+            .collect();
+        let timings = Timings::from_samples(&mut samples);
+
+        assert_eq!(timings.min, Duration::from_micros(min));
+        assert_eq!(timings.median, Duration::from_micros(median));
+        assert_eq!(timings.p95, Duration::from_micros(p95));
+    }
+}
+
+#[test]
+fn test_bench_reports() {
+    let registry = registry();
+    let puzzle = registry.get(2023, 9).unwrap();
+    let input = fs::read_to_string("input/y2023/day_9_1.txt").unwrap();
+
+    let result = bench(2023, 9, puzzle, &input, 3).unwrap();
+    assert_eq!((result.year, result.day, result.runs), (2023, 9, 3));
+    for (_, timings) in result.stages() {
+        assert!(timings.min <= timings.median && timings.median <= timings.p95);
+    }
+
+    let markdown = to_markdown(&[result]);
+    assert_eq!(markdown.lines().count(), 5);
+    assert!(markdown.contains("| 2023 | 9 | part2 | 3 |"));
+
+    let json = to_json(&[result]);
+    assert!(json.starts_with("[\n  {\"year\": 2023, \"day\": 9, \"runs\": 3,"));
+    assert_eq!(json.matches("median_ns").count(), 3);
+    assert_eq!(to_json(&[]), "[]");
+}
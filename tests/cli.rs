@@ -0,0 +1,178 @@
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command as Process, Stdio};
+
+use advent_of_code::bench::Format;
+use advent_of_code::cli::{parse_args, Command, Input};
+
+#[test]
+fn test_parse_run() {
+    let test_cases = [
+        (
+            vec!["run", "--year", "2023", "--day", "10", "--part", "2"],
+            Command::Run {
+                year: 2023,
+                day: 10,
+                part: Some(2),
+                input: Input::Path(PathBuf::from("input/y2023/day_10_1.txt")),
+                trace: false,
+            },
+        ),
+        (
+            vec!["run", "-y", "2022", "-d", "1", "-i", "day_1_2.txt"],
+            Command::Run {
+                year: 2022,
+                day: 1,
+                part: None,
+                input: Input::Path(PathBuf::from("input/y2022/day_1_2.txt")),
+                trace: false,
+            },
+        ),
+        (
+            vec!["run", "--day", "3", "--input", "./other/day_3.txt"],
+            Command::Run {
+                year: 2023,
+                day: 3,
+                part: None,
+                input: Input::Path(PathBuf::from("./other/day_3.txt")),
+                trace: false,
+            },
+        ),
+        (
+            vec!["run", "--day", "3", "--input", "-"],
+            Command::Run {
+                year: 2023,
+                day: 3,
+                part: None,
+                input: Input::Stdin,
+                trace: false,
+            },
+        ),
+        (
+            vec!["run", "--trace", "-d", "13", "-p", "1"],
+            Command::Run {
+                year: 2023,
+                day: 13,
+                part: Some(1),
+                input: Input::Path(PathBuf::from("input/y2023/day_13_1.txt")),
+                trace: true,
+            },
+        ),
+    ];
+
+    for (args, expected) in test_cases {
+        assert_eq!(parse_args(args), Ok(expected));
+    }
+}
+
+#[test]
+fn test_parse_other_commands() {
+    assert_eq!(parse_args(["list"]), Ok(Command::List));
+    assert_eq!(
+        parse_args(["all"]),
+        Ok(Command::All {
+            year: None,
+            input: 1
+        })
+    );
+    assert_eq!(
+        parse_args(["all", "--year", "2022", "--input", "2"]),
+        Ok(Command::All {
+            year: Some(2022),
+            input: 2
+        })
+    );
+    assert_eq!(
+        parse_args(["verify"]),
+        Ok(Command::Verify {
+            year: None,
+            day: None
+        })
+    );
+    assert_eq!(
+        parse_args(["verify", "-y", "2023", "-d", "9"]),
+        Ok(Command::Verify {
+            year: Some(2023),
+            day: Some(9)
+        })
+    );
+    assert_eq!(
+        parse_args(["bench", "--day", "9", "-n", "3", "--format", "json"]),
+        Ok(Command::Bench {
+            year: None,
+            day: Some(9),
+            runs: 3,
+            format: Format::Json,
+            input: 1
+        })
+    );
+    assert_eq!(
+        parse_args(["bench", "-y", "2023", "-i", "2"]),
+        Ok(Command::Bench {
+            year: Some(2023),
+            day: None,
+            runs: 10,
+            format: Format::Markdown,
+            input: 2
+        })
+    );
+    assert_eq!(parse_args(Vec::<String>::new()), Ok(Command::Help));
+}
+
+#[test]
+fn test_parse_errors() {
+    let test_cases = [
+        vec!["run"],
+        vec!["run", "--day"],
+        vec!["run", "--day", "ten"],
+        vec!["run", "--day", "1", "--part", "3"],
+        vec!["run", "--day", "1", "--verbose", "yes"],
+        vec!["bench", "--runs", "0"],
+        vec!["bench", "--format", "csv"],
+        vec!["bench", "--input", "day_9_2.txt"],
+        vec!["all", "--input", "0"],
+        vec!["frobnicate"],
+    ];
+
+    for args in test_cases {
+        assert!(parse_args(&args).is_err(), "{:?} should fail", args);
+    }
+}
+
+#[test]
+fn test_run_reads_stdin() {
+    let mut child = Process::new(env!("CARGO_BIN_EXE_main"))
+        .args(["run", "--day", "9", "--part", "2", "--input", "-"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .unwrap();
+
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(b"0 3 6 9 12 15\n1 3 6 10 15 21\n10 13 16 21 30 45\n")
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "2\n");
+}
+
+#[test]
+fn test_run_traces_to_stderr() {
+    let output = Process::new(env!("CARGO_BIN_EXE_main"))
+        .args(["run", "--day", "13", "--part", "1", "--trace"])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "405\n");
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr),
+        "block 1: horizontal None, vertical Some(5), summary 5\n\
+         block 2: horizontal Some(4), vertical None, summary 400\n"
+    );
+}
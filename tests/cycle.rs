@@ -0,0 +1,43 @@
+use advent_of_code::utils::cycle::{
+    fast_forward, find_cycle, find_cycle_brent, find_cycle_floyd, nth_state,
+};
+
+type Step = fn(&u32) -> u32;
+
+fn tail_into_loop(x: &u32) -> u32 {
+    // 0, 1, 2, 3, 4, 2, 3, 4, ..
+    if *x < 4 {
+        x + 1
+    } else {
+        2
+    }
+}
+
+#[test]
+fn test_find_cycle() {
+    let test_cases: [(u32, Step, (usize, usize)); 4] = [
+        (0, tail_into_loop, (2, 3)),
+        (0, |x| (x + 1) % 5, (0, 5)),
+        (7, |x| *x, (0, 1)),
+        (3, |x| (x * x + 1) % 255, (2, 6)),
+    ];
+
+    for (start, step, expected) in test_cases {
+        assert_eq!(find_cycle(start, step), expected, "start {}", start);
+        assert_eq!(find_cycle_floyd(start, step), expected, "start {}", start);
+        assert_eq!(find_cycle_brent(start, step), expected, "start {}", start);
+    }
+}
+
+#[test]
+fn test_fast_forward() {
+    let expected = [0, 1, 2, 3, 4, 2, 3, 4, 2, 3, 4];
+
+    for (n, state) in expected.into_iter().enumerate() {
+        assert_eq!(nth_state(0, tail_into_loop, n), state, "n = {}", n);
+        assert_eq!(expected[fast_forward(2, 3, n)], state, "n = {}", n);
+    }
+
+    assert_eq!(nth_state(0, tail_into_loop, 1_000_000_000), 4);
+    assert_eq!(fast_forward(2, 3, 1_000_000_000), 4);
+}
@@ -0,0 +1,47 @@
+use advent_of_code::utils::geometry::{
+    boundary_points, interior_points, shoelace_area2,
+};
+
+type Polygon = &'static [(i64, i64)];
+
+#[test]
+fn test_geometry() {
+    let test_cases: [(Polygon, i64, i64, i64); 6] = [
+        // 4x3 rectangle, clockwise and counter-clockwise
+        (&[(0, 0), (4, 0), (4, 3), (0, 3)], 24, 14, 6),
+        (&[(0, 3), (4, 3), (4, 0), (0, 0)], 24, 14, 6),
+        // Right triangle with a diagonal through lattice points
+        (&[(0, 0), (4, 0), (0, 4)], 16, 12, 3),
+        // Unit-step loop around a single tile, as walked in a grid
+        (
+            &[
+                (1, 0),
+                (2, 0),
+                (2, 1),
+                (2, 2),
+                (1, 2),
+                (0, 2),
+                (0, 1),
+                (0, 0),
+            ],
+            8,
+            8,
+            1,
+        ),
+        // L-shape
+        (&[(0, 0), (2, 0), (2, 1), (1, 1), (1, 2), (0, 2)], 6, 8, 0),
+        // Degenerate polygons have no inside
+        (&[(0, 0), (3, 0)], 0, 6, 0),
+    ];
+
+    for (vertices, expected_area2, expected_boundary, expected_interior) in
+        test_cases.iter()
+    {
+        assert_eq!(shoelace_area2(vertices), *expected_area2);
+        assert_eq!(boundary_points(vertices), *expected_boundary);
+        assert_eq!(interior_points(vertices), *expected_interior);
+    }
+
+    assert_eq!(shoelace_area2(&[]), 0);
+    assert_eq!(interior_points(&[]), 0);
+}
@@ -0,0 +1,102 @@
+use std::collections::HashMap;
+
+use advent_of_code::utils::graph::{
+    astar, bfs, connected_components, dijkstra, from_fn, GridGraph,
+};
+use advent_of_code::utils::grid::Grid;
+
+fn digits(input: &str) -> Grid<u64> {
+    Grid::from_reader(input.as_bytes(), |c| {
+        c.to_digit(10).map(u64::from).ok_or("expected a digit")
+    })
+    .unwrap()
+}
+
+#[test]
+fn test_bfs() {
+    let graph: HashMap<&str, Vec<&str>> = HashMap::from([
+        ("a", vec!["b", "c"]),
+        ("b", vec!["d"]),
+        ("c", vec!["d"]),
+        ("d", vec!["e"]),
+        ("f", vec!["a"]),
+    ]);
+    let paths = bfs(&graph, "a");
+
+    assert_eq!(*paths.start(), "a");
+    assert_eq!(paths.distance(&"a"), Some(0));
+    assert_eq!(paths.distance(&"e"), Some(3));
+    assert_eq!(paths.distance(&"f"), None);
+    assert_eq!(paths.distances().len(), 5);
+    assert_eq!(paths.path_to(&"e").unwrap().len(), 4);
+    assert_eq!(paths.path_to(&"a"), Some(vec!["a"]));
+    assert_eq!(paths.path_to(&"f"), None);
+}
+
+#[test]
+fn test_dijkstra_and_astar() {
+    let grid = digits("1163751\n1381373\n2136511\n3694931\n7463417");
+    // Entering a cell costs its value
+    let graph = GridGraph::new(&grid, |_, to| Some(grid[to]));
+    let goal = (grid.width() - 1, grid.height() - 1);
+
+    let paths = dijkstra(&graph, (0, 0));
+    let path = paths.path_to(&goal).unwrap();
+    let cost: u64 = path.iter().skip(1).map(|&position| grid[position]).sum();
+
+    assert_eq!(paths.distance(&goal), Some(cost));
+    assert_eq!(path.first(), Some(&(0, 0)));
+    assert_eq!(path.last(), Some(&goal));
+
+    // Every step costs at least 1, so the Manhattan distance never
+    // overestimates
+    let (astar_cost, astar_path) = astar(
+        &graph,
+        (0, 0),
+        |&position| position == goal,
+        |&(x, y)| (goal.0 - x + goal.1 - y) as u64,
+    )
+    .unwrap();
+
+    assert_eq!(astar_cost, cost);
+    assert_eq!(astar_path.last(), Some(&goal));
+    assert!(astar(&graph, (0, 0), |_| false, |_| 0).is_none());
+
+    // Unlike Dijkstra, breadth-first search only counts steps
+    assert_eq!(bfs(&graph, (0, 0)).distance(&goal), Some(10));
+}
+
+#[test]
+fn test_from_fn() {
+    // Doubling costs 1 and adding one costs 1, up to 100
+    let graph = from_fn(|&n: &u32| {
+        [(n * 2, 1), (n + 1, 1)]
+            .into_iter()
+            .filter(|&(next, _)| next <= 100)
+            .collect()
+    });
+    let paths = dijkstra(&graph, 1);
+
+    assert_eq!(paths.distance(&64), Some(6));
+    assert_eq!(paths.distance(&100), Some(8));
+    assert_eq!(paths.path_to(&5), Some(vec![1, 2, 4, 5]));
+}
+
+#[test]
+fn test_connected_components() {
+    let grid =
+        Grid::from_reader("aab\nbab\nbbc".as_bytes(), Ok::<_, &str>).unwrap();
+    let graph =
+        GridGraph::new(&grid, |from, to| (grid[from] == grid[to]).then_some(1));
+
+    let components =
+        connected_components(&graph, grid.iter().map(|(position, _)| position));
+    let sizes: Vec<(char, usize)> = components
+        .iter()
+        .map(|component| (grid[component[0]], component.len()))
+        .collect();
+
+    assert_eq!(sizes, [('a', 3), ('b', 2), ('b', 3), ('c', 1)]);
+
+    assert!(connected_components(&graph, []).is_empty());
+}
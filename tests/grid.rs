@@ -0,0 +1,120 @@
+use advent_of_code::error::AocError;
+use advent_of_code::utils::grid::Grid;
+
+fn sample() -> Grid<char> {
+    // abc
+    // def
+    Grid::from_reader("abc\ndef\n".as_bytes(), Ok::<char, &str>).unwrap()
+}
+
+#[test]
+fn test_grid_parse() {
+    let grid = sample();
+
+    assert_eq!((grid.width(), grid.height()), (3, 2));
+    assert_eq!(grid[(2, 0)], 'c');
+    assert_eq!(grid[(0, 1)], 'd');
+    assert_eq!(grid.get(3, 0), None);
+    assert_eq!(grid.get(0, 2), None);
+    assert_eq!(grid.cells(), &['a', 'b', 'c', 'd', 'e', 'f']);
+
+    let digits =
+        Grid::from_lines(["12", "34"], 1, |c| c.to_digit(10).ok_or("digit"));
+    assert_eq!(digits.unwrap(), Grid::new(2, 2, vec![1, 2, 3, 4]));
+
+    let empty = Grid::from_reader("".as_bytes(), Ok::<char, &str>).unwrap();
+    assert!(empty.is_empty());
+}
+
+#[test]
+fn test_grid_parse_errors() {
+    let test_cases = [
+        ("..\n.x\n", 3, (4, 2)),
+        ("...\n..\n", 1, (2, 3)),
+        ("..\n...\n", 1, (2, 3)),
+    ];
+
+    for (input, first_line, expected) in test_cases {
+        let result = Grid::from_lines(input.lines(), first_line, |c| match c {
+            '.' => Ok(c),
+            _ => Err("expected `.`"),
+        });
+
+        match result {
+            Err(AocError::Parse { line, column, .. }) => {
+                assert_eq!((line, column), expected, "{:?}", input);
+            }
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+    }
+}
+
+#[test]
+fn test_grid_neighbors() {
+    let grid = Grid::new(3, 3, (0..9).collect());
+
+    let corner: Vec<_> = grid.neighbors4(0, 0).collect();
+    assert_eq!(corner, vec![(1, 0), (0, 1)]);
+
+    let center: Vec<_> = grid.neighbors4(1, 1).collect();
+    assert_eq!(center, vec![(1, 0), (2, 1), (1, 2), (0, 1)]);
+
+    let corner: Vec<_> = grid.neighbors8(2, 2).collect();
+    assert_eq!(corner, vec![(1, 1), (2, 1), (1, 2)]);
+    assert_eq!(grid.neighbors8(1, 1).count(), 8);
+
+    assert_eq!(grid.offset((0, 0), (-1, 0)), None);
+    assert_eq!(grid.offset((0, 0), (2, 2)), Some((2, 2)));
+    assert_eq!(grid.offset((0, 0), (3, 0)), None);
+}
+
+#[test]
+fn test_grid_views() {
+    let mut grid = sample();
+
+    assert_eq!(grid.row(1), &['d', 'e', 'f']);
+    let rows: Vec<String> =
+        grid.rows().map(|row| row.iter().collect()).collect();
+    assert_eq!(rows, vec!["abc", "def"]);
+
+    let column: String = grid.column(1).collect();
+    assert_eq!(column, "be");
+    let columns: Vec<String> =
+        grid.columns().map(|column| column.collect()).collect();
+    assert_eq!(columns, vec!["ad", "be", "cf"]);
+
+    assert_eq!(grid.iter().nth(4), Some(((1, 1), &'e')));
+    assert_eq!(grid.find(&'f'), Some((2, 1)));
+    assert_eq!(grid.position(|&c| c > 'b'), Some((2, 0)));
+    assert_eq!(grid.find(&'z'), None);
+
+    grid[(0, 0)] = 'z';
+    grid.row_mut(1)[2] = 'y';
+    *grid.get_mut(1, 1).unwrap() = 'x';
+    assert_eq!(grid.to_string(), "zbc\ndxy");
+    assert_eq!(grid.map(|&c| c == 'x').find(&true), Some((1, 1)));
+}
+
+#[test]
+fn test_grid_transforms() {
+    let grid = sample();
+    let test_cases = [
+        (grid.transpose(), "ad\nbe\ncf"),
+        (grid.rotate_clockwise(), "da\neb\nfc"),
+        (grid.rotate_counter_clockwise(), "cf\nbe\nad"),
+        (grid.flip_horizontal(), "cba\nfed"),
+        (grid.flip_vertical(), "def\nabc"),
+    ];
+
+    for (transformed, expected) in test_cases {
+        assert_eq!(transformed.to_string(), expected);
+    }
+
+    let mut rotated = grid.clone();
+    for _ in 0..4 {
+        rotated = rotated.rotate_clockwise();
+    }
+    assert_eq!(rotated, grid);
+    assert_eq!(grid.rotate_clockwise().rotate_counter_clockwise(), grid);
+    assert_eq!(grid.transpose().transpose(), grid);
+}
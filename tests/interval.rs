@@ -0,0 +1,93 @@
+use advent_of_code::utils::interval::{Interval, IntervalSet};
+
+fn set(intervals: &[(i32, i32)]) -> IntervalSet<i32> {
+    intervals
+        .iter()
+        .map(|&(start, end)| Interval::new(start, end))
+        .collect()
+}
+
+fn pairs(set: &IntervalSet<i32>) -> Vec<(i32, i32)> {
+    set.intervals()
+        .iter()
+        .map(|interval| (interval.start, interval.end))
+        .collect()
+}
+
+#[test]
+fn test_interval() {
+    let interval = Interval::new(2, 6);
+
+    assert!(interval.contains(2) && interval.contains(5));
+    assert!(!interval.contains(6));
+    assert!(Interval::new(3, 3).is_empty());
+    assert_eq!(Interval::from_len(10u64, 5), Interval::new(10, 15));
+    assert_eq!(
+        Interval::checked_from_len(10u64, 5),
+        Some(Interval::new(10, 15))
+    );
+    assert_eq!(Interval::checked_from_len(u8::MAX, 1), None);
+
+    assert_eq!(
+        interval.intersect(&Interval::new(4, 9)),
+        Some(Interval::new(4, 6))
+    );
+    assert_eq!(interval.intersect(&Interval::new(6, 9)), None);
+
+    assert_eq!(
+        interval.split_at(4),
+        (Some(Interval::new(2, 4)), Some(Interval::new(4, 6)))
+    );
+    assert_eq!(interval.split_at(1), (None, Some(interval)));
+
+    assert_eq!(
+        interval.subtract(&Interval::new(3, 4)),
+        (Some(Interval::new(2, 3)), Some(Interval::new(4, 6)))
+    );
+    assert_eq!(interval.subtract(&Interval::new(0, 9)), (None, None));
+
+    assert_eq!(interval.map_offset(2, 100), Interval::new(100, 104));
+}
+
+#[test]
+fn test_interval_set() {
+    let a = set(&[(5, 8), (0, 2), (1, 3), (8, 9), (20, 20)]);
+    let b = set(&[(2, 6), (10, 12)]);
+
+    assert_eq!(pairs(&a), vec![(0, 3), (5, 9)]);
+    assert_eq!(a.min(), Some(0));
+    assert!(a.contains(8) && !a.contains(3) && !a.contains(9));
+
+    assert_eq!(pairs(&a.union(&b)), vec![(0, 9), (10, 12)]);
+    assert_eq!(pairs(&a.intersect(&b)), vec![(2, 3), (5, 6)]);
+    assert_eq!(pairs(&a.subtract(&b)), vec![(0, 2), (6, 9)]);
+    assert_eq!(pairs(&b.subtract(&a)), vec![(3, 5), (10, 12)]);
+
+    let (below, above) = a.split_at(6);
+    assert_eq!(pairs(&below), vec![(0, 3), (5, 6)]);
+    assert_eq!(pairs(&above), vec![(6, 9)]);
+
+    assert!(IntervalSet::<i32>::new().is_empty());
+    assert!(a.intersect(&IntervalSet::new()).is_empty());
+}
+
+#[test]
+fn test_interval_set_map_offsets() {
+    // seed-to-soil map of the day 5 example
+    let seeds = set(&[(79, 93), (55, 68)]);
+    let soil = seeds.map_offsets([
+        (Interval::from_len(98, 2), 50),
+        (Interval::from_len(50, 48), 52),
+    ]);
+    assert_eq!(pairs(&soil), vec![(57, 70), (81, 95)]);
+
+    // parts outside of every source keep their values, overlapping sources
+    // map by the first one
+    let values = set(&[(0, 10)]);
+    let mapped = values
+        .map_offsets([(Interval::new(2, 4), 100), (Interval::new(3, 6), 200)]);
+    assert_eq!(
+        pairs(&mapped),
+        vec![(0, 2), (6, 10), (100, 102), (201, 203)]
+    );
+}
@@ -0,0 +1,160 @@
+use advent_of_code::utils::math::{
+    checked_lcm, crt, extended_gcd, extrapolate, forward_differences, gcd,
+    isqrt, lcm, mod_inverse, mod_pow, mul_mod, rem_euclid,
+};
+use proptest::prelude::*;
+
+#[test]
+fn test_math() {
+    assert_eq!(gcd(12u32, 18), 6);
+    assert_eq!(gcd(-12i32, 18), 6);
+    assert_eq!(gcd(0u8, 0), 0);
+    assert_eq!(lcm(4u64, 6), 12);
+    assert_eq!(lcm(-4i64, 6), 12);
+    assert_eq!(lcm(0usize, 6), 0);
+    assert_eq!(checked_lcm(200u8, 3), None);
+    assert_eq!(checked_lcm(u64::MAX, u64::MAX), Some(u64::MAX));
+
+    assert_eq!(extended_gcd(240i32, 46), (2, -9, 47));
+    assert_eq!(isqrt(0u8), 0);
+    assert_eq!(isqrt(u8::MAX), 15);
+    assert_eq!(isqrt(i32::MAX), 46340);
+    assert_eq!(isqrt(u128::MAX), u128::from(u64::MAX));
+    assert_eq!(rem_euclid(-7i32, 3), 2);
+    assert_eq!(mul_mod(u64::MAX - 1, u64::MAX - 1, u64::MAX), 1);
+    // Fermat's little theorem, without overflowing a u8
+    assert_eq!(mod_pow(2u8, 250, 251), 1);
+    assert_eq!(mod_pow(2u8, 7, 251), 128);
+    assert_eq!(mod_pow(-2i32, 3, 5), 2);
+    assert_eq!(mod_inverse(3u8, 7), Some(5));
+    assert_eq!(mod_inverse(4u8, 6), None);
+    assert_eq!(mod_inverse(5u8, 1), Some(0));
+
+    assert_eq!(crt([(2u32, 3), (3, 5), (2, 7)]), Some((23, 105)));
+    assert_eq!(crt([(3u32, 4), (5, 6)]), Some((11, 12)));
+    assert_eq!(crt([(1u32, 4), (2, 6)]), None);
+    assert_eq!(crt([(-1i32, 4)]), Some((3, 4)));
+    assert_eq!(crt(Vec::<(u8, u8)>::new()), Some((0, 1)));
+    // 251 * 241 does not fit in a u8 modulus
+    assert_eq!(crt([(0u8, 251), (0, 241)]), None);
+
+    assert_eq!(
+        forward_differences(&[1, 3, 6, 10, 15, 21]),
+        Some(vec![1, 2, 1])
+    );
+    assert_eq!(forward_differences(&[0u8, 0]), Some(vec![]));
+    assert_eq!(forward_differences(&[1u8, 0]), None);
+    assert_eq!(extrapolate(&[1, 3, 6, 10, 15, 21], 6), Some(28));
+    assert_eq!(extrapolate(&[10, 13, 16, 21, 30, 45], -1), Some(5));
+    assert_eq!(extrapolate(&[10, 13, 16, 21, 30, 45], -3), Some(-19));
+    assert_eq!(extrapolate(&[7], 100), Some(7));
+    assert_eq!(extrapolate::<i32>(&[], 0), None);
+    assert_eq!(extrapolate(&[0i8, 100], 2), None);
+}
+
+proptest! {
+    #[test]
+    fn test_gcd_lcm(a in 0u64..=u64::MAX, b in 0u64..=u64::MAX) {
+        let g = gcd(a, b);
+
+        prop_assert_eq!(g, gcd(b, a));
+        if g != 0 {
+            prop_assert_eq!((a % g, b % g), (0, 0));
+            prop_assert_eq!(gcd(a / g, b / g), 1);
+        }
+
+        let expected = if g == 0 { 0 } else { a as u128 / g as u128 * b as u128 };
+        prop_assert_eq!(checked_lcm(a, b), u64::try_from(expected).ok());
+        prop_assert_eq!(lcm(a as u128, b as u128), expected);
+    }
+
+    #[test]
+    fn test_isqrt(n in any::<u128>()) {
+        let root = isqrt(n);
+
+        prop_assert!(root * root <= n);
+        prop_assert!((root + 1).checked_mul(root + 1).map_or(true, |r| r > n));
+    }
+
+    #[test]
+    fn test_extrapolate(
+        coefficients in prop::collection::vec(-100i64..100, 1..6),
+        extra in 0usize..4,
+        x in -50i64..50,
+    ) {
+        let evaluate = |x: i64| {
+            coefficients.iter().rev().fold(0, |value, &c| value * x + c)
+        };
+        // Enough points to pin down the polynomial, maybe more
+        let values: Vec<i64> =
+            (0..(coefficients.len() + extra) as i64).map(evaluate).collect();
+
+        prop_assert_eq!(extrapolate(&values, x), Some(evaluate(x)));
+        prop_assert!(
+            forward_differences(&values).unwrap().len() <= coefficients.len()
+        );
+    }
+
+    #[test]
+    fn test_extended_gcd(a in any::<i32>(), b in any::<i32>()) {
+        let (g, x, y) = extended_gcd(i64::from(a), i64::from(b));
+
+        prop_assert_eq!(g, gcd(i64::from(a), i64::from(b)));
+        prop_assert_eq!(i64::from(a) * x + i64::from(b) * y, g);
+    }
+
+    #[test]
+    fn test_mul_mod_and_mod_pow(
+        a in any::<u64>(),
+        b in any::<u64>(),
+        exponent in 0u64..200,
+        modulus in 1u64..=u64::MAX,
+    ) {
+        let expected = (a as u128 * b as u128 % modulus as u128) as u64;
+        prop_assert_eq!(mul_mod(a, b, modulus), expected);
+
+        let mut power = 1 % modulus as u128;
+        for _ in 0..exponent {
+            power = power * a as u128 % modulus as u128;
+        }
+        prop_assert_eq!(mod_pow(a, exponent, modulus), power as u64);
+    }
+
+    #[test]
+    fn test_mod_inverse(a in any::<u64>(), modulus in 1u64..=u64::MAX) {
+        match mod_inverse(a, modulus) {
+            Some(inverse) => {
+                prop_assert!(inverse < modulus);
+                prop_assert_eq!(mul_mod(a, inverse, modulus), 1 % modulus);
+            }
+            None => prop_assert_ne!(gcd(a, modulus), 1),
+        }
+    }
+
+    #[test]
+    fn test_crt(
+        x in 0u64..1_000_000_000,
+        moduli in prop::collection::vec(1u64..2_000, 0..5),
+        offset in 0u64..2_000,
+    ) {
+        let congruences: Vec<(u64, u64)> =
+            moduli.iter().map(|&m| (x % m, m)).collect();
+        let expected_modulus = moduli.iter().fold(1, |l, &m| lcm(l, m));
+
+        // Any system built from a common solution is consistent
+        prop_assert_eq!(
+            crt(congruences.iter().copied()),
+            Some((x % expected_modulus, expected_modulus))
+        );
+
+        // Moving one residue breaks it exactly when the shift is not a
+        // multiple of the gcd with the other moduli
+        if let Some((&(residue, m), rest)) = congruences.split_last() {
+            let others = rest.iter().fold(1, |l, &(_, n)| lcm(l, n));
+            let shifted = rest.iter().copied().chain([(residue + offset, m)]);
+            let consistent = offset % gcd(m, others) == 0;
+
+            prop_assert_eq!(crt(shifted).is_some(), consistent);
+        }
+    }
+}
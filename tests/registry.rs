@@ -0,0 +1,36 @@
+use std::fs;
+
+use advent_of_code::{registry, solution::Answer};
+
+#[test]
+fn test_registry_covers_every_day() {
+    let registry = registry();
+
+    assert_eq!(registry.years(), vec![2022, 2023]);
+    for day in 1..=2 {
+        assert!(registry.get(2022, day).is_some(), "missing 2022 day {day}");
+    }
+    for day in 1..=15 {
+        assert!(registry.get(2023, day).is_some(), "missing 2023 day {day}");
+    }
+    assert!(registry.get(2023, 25).is_none());
+}
+
+#[test]
+fn test_registry_runs_solution() {
+    let registry = registry();
+    let test_cases = [
+        (2022, 1, "input/y2022/day_1_1.txt", 24000, 45000),
+        (2023, 2, "input/y2023/day_2_1.txt", 8, 2286),
+        (2023, 9, "input/y2023/day_9_1.txt", 114, 2),
+    ];
+
+    for (year, day, file_path, expected_part1, expected_part2) in test_cases {
+        let puzzle = registry.get(year, day).unwrap();
+        let input = fs::read_to_string(file_path).unwrap();
+        let (part1, part2) = puzzle.run(&input).unwrap();
+
+        assert_eq!(part1, Answer::Number(expected_part1));
+        assert_eq!(part2, Answer::Number(expected_part2));
+    }
+}
@@ -0,0 +1,52 @@
+use advent_of_code::trace;
+use advent_of_code::y2022::day_1::process_str as process_str_2022_1;
+use advent_of_code::y2023::day_1::sum_calibration_values;
+use advent_of_code::y2023::day_13::{get_sum, process_str as process_str_13};
+
+#[test]
+fn test_capture() {
+    let (sum, lines) = trace::capture(|| {
+        let blocks = process_str_13(
+            "#.##..##.\n..#.##.#.\n##......#\n##......#\n..#.##.#.\n\
+             ..##..##.\n#.#.##.#.\n",
+        )
+        .unwrap();
+        get_sum(&blocks, 0).unwrap()
+    });
+    assert_eq!(sum, 5);
+    assert_eq!(
+        lines,
+        ["block 1: horizontal None, vertical Some(5), summary 5"]
+    );
+
+    let (totals, lines) =
+        trace::capture(|| process_str_2022_1("1\n2\n\n3\n").unwrap());
+    assert_eq!(totals, (3, 6));
+    assert_eq!(lines, ["elf 1: 3 calories", "elf 2: 3 calories"]);
+
+    let calibration = ["a1b2".to_string(), "x7y".to_string()];
+    let (total, lines) =
+        trace::capture(|| sum_calibration_values(&calibration, false).unwrap());
+    assert_eq!(total, 89);
+    assert_eq!(lines, ["\"a1b2\": 12", "\"x7y\": 77"]);
+}
+
+#[test]
+fn test_no_sink() {
+    assert!(!trace::is_enabled());
+    advent_of_code::trace!("{}", unreachable_if_formatted());
+
+    // Nested sinks are restored when their run ends
+    let ((), outer) = trace::capture(|| {
+        advent_of_code::trace!("outer");
+        let ((), inner) = trace::capture(|| advent_of_code::trace!("inner"));
+        assert_eq!(inner, ["inner"]);
+        advent_of_code::trace!("outer again");
+    });
+    assert_eq!(outer, ["outer", "outer again"]);
+    assert!(!trace::is_enabled());
+}
+
+fn unreachable_if_formatted() -> &'static str {
+    panic!("arguments are only evaluated while tracing")
+}
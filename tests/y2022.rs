@@ -11,7 +11,7 @@ fn test_day_1() {
     ];
 
     for (file_path, expected_max, expected_sum_top_3) in test_cases.iter() {
-        let (max, sum_top_3) = process_file_1(file_path);
+        let (max, sum_top_3) = process_file_1(file_path).unwrap();
 
         assert_eq!(max, *expected_max);
         assert_eq!(sum_top_3, *expected_sum_top_3);
@@ -28,7 +28,8 @@ fn test_day_2() {
     for (file_path, expected_total_score, expected_total_converted_score) in
         test_cases.iter()
     {
-        let (total_score, total_converted_score) = process_file_2(file_path);
+        let (total_score, total_converted_score) =
+            process_file_2(file_path).unwrap();
 
         assert_eq!(total_score, *expected_total_score);
         assert_eq!(total_converted_score, *expected_total_converted_score);
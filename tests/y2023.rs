@@ -1,12 +1,31 @@
+use advent_of_code::error::AocError;
+use advent_of_code::utils::interval::{Interval, IntervalSet};
 use advent_of_code::y2023::{
     day_1::*,
-    day_10::process_file as process_file_10,
+    day_10::{
+        process_file as process_file_10, process_str as process_str_10,
+        read_maze,
+    },
     day_11::{
         get_sum_length, get_sum_length_n, process_file as process_file_11,
+        process_str as process_str_11,
+    },
+    day_12::{
+        count_arrangements, process_file as process_file_12, sum_arrangements,
+        sum_unfolded_arrangements,
+    },
+    day_13::{
+        find_reflection, get_sum, process_file as process_file_13,
+        process_str as process_str_13,
+    },
+    day_14::{
+        measure_load_after, measure_north_load,
+        process_file as process_file_14, process_str as process_str_14, Tilt,
+    },
+    day_15::{
+        focusing_power, hash, process_file as process_file_15,
+        process_str as process_str_15, sum_of_hashes,
     },
-    day_12::process_file as process_file_12,
-    day_13::{get_sum, process_file as process_file_13},
-    day_14::{measure_load, process_file as process_file_14, tilt_north},
     day_2::{
         calculate_power_max_set, is_game_possible, read_games_from_file,
         CubeCounts,
@@ -16,13 +35,27 @@ use advent_of_code::y2023::{
         process_file as process_file_3,
     },
     day_4::{find_matching_card, process_file as process_file_4},
-    day_5::{find_lowest_location, process_file as process_file_5},
+    day_5::{
+        find_lowest_location, find_lowest_seed_location,
+        find_lowest_seed_range_location, process_file as process_file_5,
+        process_str as process_str_5,
+    },
     day_6::{
         count_number_of_ways_to_beat_record, process_file as process_file_6,
+        ways_to_beat_combined_record, ways_to_beat_each_record,
+    },
+    day_7::{
+        calculate_total, process_file as process_file_7,
+        process_str as process_str_7, Hand, HandType, Rules,
+    },
+    day_8::{
+        find_ghost_steps, process_file as process_file_8,
+        process_str as process_str_8, traverse_graph,
+    },
+    day_9::{
+        process_file as process_file_9, process_str as process_str_9,
+        read_histories, sum_extrapolated, Side,
     },
-    day_7::{calculate_total, process_file as process_file_7},
-    day_8::{find_steps_lcm, process_file as process_file_8, traverse_graph},
-    day_9::process_file as process_file_9,
 };
 
 #[test]
@@ -34,13 +67,14 @@ fn test_day_1() {
     ];
 
     for (file_path, expected_total) in test_cases.iter() {
-        match process_file(file_path) {
-            Ok(total) => {
-                assert_eq!(total, *expected_total);
-            }
-            Err(err) => eprintln!("Error reading file {}: {}", file_path, err),
-        }
+        let total = process_file(file_path).unwrap();
+        assert_eq!(total, *expected_total);
     }
+
+    assert!(matches!(
+        process_str("1abc2\n\nxyz\n"),
+        Err(AocError::Parse { line: 3, .. })
+    ));
 }
 
 #[test]
@@ -59,29 +93,22 @@ fn test_day_2() {
     .collect();
 
     for (file_path, expected_sum, expected_power_max) in test_cases.iter() {
-        match read_games_from_file(file_path) {
-            Ok(games) => {
-                // for (i, game) in games.iter().enumerate() {
-                //     println!("Game {}: {:?}", i + 1, game);
-                // }
-
-                let possible_games: Vec<usize> = games
-                    .iter()
-                    .filter(|&game| is_game_possible(game, &cube_counts))
-                    .map(|game| game.id)
-                    .collect();
-
-                let sum: usize = possible_games.iter().sum();
-                println!("Possible games: {:?}", possible_games);
-                println!("Sum of IDs: {}", sum);
-                assert_eq!(sum, *expected_sum);
-
-                let power_max = calculate_power_max_set(&games);
-                println!("Power max cubes: {}", power_max);
-                assert_eq!(power_max, *expected_power_max);
-            }
-            Err(err) => eprintln!("Error reading file: {}", err),
-        }
+        let games = read_games_from_file(file_path).unwrap();
+        // for (i, game) in games.iter().enumerate() {
+        //     println!("Game {}: {:?}", i + 1, game);
+        // }
+
+        let possible_games: Vec<usize> = games
+            .iter()
+            .filter(|&game| is_game_possible(game, &cube_counts))
+            .map(|game| game.id)
+            .collect();
+
+        let sum: usize = possible_games.iter().sum();
+        assert_eq!(sum, *expected_sum);
+
+        let power_max = calculate_power_max_set(&games);
+        assert_eq!(power_max, *expected_power_max);
     }
 }
 
@@ -94,22 +121,17 @@ fn test_day_3() {
 
     for (file_path, expected_sum, expected_sum_gear_ratios) in test_cases.iter()
     {
-        match process_file_3(file_path) {
-            Ok((part_numbers, lines)) => {
-                // for (i, part_number) in part_numbers.iter().enumerate() {
-                //     println!("Row {}: {:?}", i + 1, part_number);
-                // }
-                let (result_parts, gears) =
-                    check_surroundings(&part_numbers, &lines);
-
-                let sum = calculate_sum(&result_parts);
-                let sum_gear_ratios = calculate_sum_of_gear_ratios(&gears);
-
-                assert_eq!(sum, *expected_sum);
-                assert_eq!(sum_gear_ratios, *expected_sum_gear_ratios);
-            }
-            Err(err) => eprintln!("Error reading file: {}", err),
-        }
+        let (part_numbers, lines) = process_file_3(file_path).unwrap();
+        // for (i, part_number) in part_numbers.iter().enumerate() {
+        //     println!("Row {}: {:?}", i + 1, part_number);
+        // }
+        let (result_parts, gears) = check_surroundings(&part_numbers, &lines);
+
+        let sum = calculate_sum(&result_parts);
+        let sum_gear_ratios = calculate_sum_of_gear_ratios(&gears);
+
+        assert_eq!(sum, *expected_sum);
+        assert_eq!(sum_gear_ratios, *expected_sum_gear_ratios);
     }
 }
 
@@ -123,16 +145,13 @@ fn test_day_4() {
     for (file_path, expected_sum, expected_total_scratch_cards) in
         test_cases.iter()
     {
-        match process_file_4(file_path) {
-            Ok((winning_numbers, having_numbers)) => {
-                let (sum, total_scratch_cards) =
-                    find_matching_card(&winning_numbers, &having_numbers);
+        let (winning_numbers, having_numbers) =
+            process_file_4(file_path).unwrap();
+        let (sum, total_scratch_cards) =
+            find_matching_card(&winning_numbers, &having_numbers);
 
-                assert_eq!(sum, *expected_sum);
-                assert_eq!(total_scratch_cards, *expected_total_scratch_cards);
-            }
-            Err(err) => eprintln!("Error reading file: {}", err),
-        }
+        assert_eq!(sum, *expected_sum);
+        assert_eq!(total_scratch_cards, *expected_total_scratch_cards);
     }
 }
 
@@ -146,20 +165,143 @@ fn test_day_5() {
     for (file_path, expected_lowest, expected_lowest_for_seed_pairs) in
         test_cases.iter()
     {
-        match process_file_5(file_path) {
-            Ok(map_data) => {
-                let (min_location, min_location_for_seed_pairs) =
-                    find_lowest_location(&map_data);
+        let map_data = process_file_5(file_path).unwrap();
+        let (min_location, min_location_for_seed_pairs) =
+            find_lowest_location(&map_data).unwrap();
+
+        assert_eq!(min_location, *expected_lowest);
+        assert_eq!(
+            min_location_for_seed_pairs,
+            *expected_lowest_for_seed_pairs
+        );
+    }
+}
 
-                assert_eq!(min_location, *expected_lowest);
-                assert_eq!(
-                    min_location_for_seed_pairs,
-                    *expected_lowest_for_seed_pairs
-                );
+#[test]
+fn test_day_5_map_chain() {
+    let map_data = process_file_5("input/y2023/day_5_1.txt").unwrap();
+    let maps = &map_data.maps;
+
+    assert_eq!(
+        maps.categories().collect::<Vec<_>>(),
+        [
+            "seed",
+            "soil",
+            "fertilizer",
+            "water",
+            "light",
+            "temperature",
+            "humidity",
+            "location"
+        ]
+    );
+    assert_eq!(maps.map_value("seed", "soil", 79).unwrap(), 81);
+    assert_eq!(maps.map_value("seed", "location", 79).unwrap(), 82);
+    assert_eq!(maps.map_value("soil", "water", 81).unwrap(), 81);
+    assert_eq!(maps.map_value("light", "light", 5).unwrap(), 5);
+    // Backwards, to the lowest value that maps onto the given one
+    assert_eq!(maps.map_value("location", "seed", 82).unwrap(), 79);
+    assert_eq!(maps.map_value("soil", "seed", 81).unwrap(), 79);
+    assert_eq!(maps.map_value("soil", "seed", 50).unwrap(), 98);
+    let locations = IntervalSet::from_iter([Interval::new(46, 47)]);
+    let seeds = maps.map_ranges("location", "seed", locations).unwrap();
+    for seed in 0..100 {
+        let location = maps.map_value("seed", "location", seed).unwrap();
+        assert_eq!(seeds.contains(seed), location == 46, "seed {}", seed);
+    }
+    let map_data = process_str_5("seeds: 1 1\n\na-to-b map:\n0 5 5\n").unwrap();
+    // 5 maps to 0 and 0 to itself, but nothing maps to 5
+    assert_eq!(map_data.maps.map_value("b", "a", 0).unwrap(), 0);
+    assert!(matches!(
+        map_data.maps.map_value("b", "a", 5),
+        Err(AocError::Unsolvable(_))
+    ));
+    assert!(matches!(
+        maps.map_value("seed", "planet", 79),
+        Err(AocError::Unsolvable(_))
+    ));
+
+    // Any number of stages, as long as they link up
+    let map_data =
+        process_str_5("seeds: 1 2\n\na-to-b map:\n10 0 5\n").unwrap();
+    assert_eq!(map_data.maps.map_value("a", "b", 3).unwrap(), 13);
+
+    let unlinked = "seeds: 1 1\n\na-to-b map:\n0 0 1\n\nc-to-d map:\n0 0 1\n";
+    match process_str_5(unlinked) {
+        Err(AocError::Parse { line, column, .. }) => {
+            assert_eq!((line, column), (6, 1));
+        }
+        other => panic!("expected a parse error, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_day_5_seed_ranges() {
+    match process_str_5("seeds: 79 14\n55 13\n\nseed-to-location map:\n") {
+        Err(AocError::Parse { line, column, .. }) => {
+            assert_eq!((line, column), (2, 1));
+        }
+        other => panic!("expected a parse error, got {:?}", other),
+    }
+
+    // Single seeds are fine for part 1, but part 2 needs them in pairs
+    let map_data =
+        process_str_5("seeds: 79 14 55\n\nseed-to-location map:\n0 0 1\n")
+            .unwrap();
+    assert_eq!(find_lowest_seed_location(&map_data).unwrap(), 14);
+    assert!(matches!(
+        find_lowest_seed_range_location(&map_data),
+        Err(AocError::Unsolvable(_))
+    ));
+
+    let map_data =
+        process_str_5("seeds:\n\nseed-to-location map:\n0 0 1\n").unwrap();
+    assert!(matches!(
+        find_lowest_seed_location(&map_data),
+        Err(AocError::Unsolvable(_))
+    ));
+    assert!(matches!(
+        find_lowest_seed_range_location(&map_data),
+        Err(AocError::Unsolvable(_))
+    ));
+}
+
+#[test]
+fn test_day_5_overflow() {
+    let max = u128::MAX;
+    let test_cases = [(format!("{} 0 2", max), 1), (format!("0 {} 2", max), 3)];
+
+    for (rule, expected_column) in test_cases {
+        let input = format!("seeds: 1\n\nseed-to-location map:\n{}\n", rule);
+        match process_str_5(&input) {
+            Err(AocError::Parse { line, column, .. }) => {
+                assert_eq!((line, column), (4, expected_column), "{}", rule);
             }
-            Err(err) => eprintln!("Error reading file: {}", err),
+            other => panic!("expected a parse error, got {:?}", other),
         }
     }
+
+    // A range may end exactly at u128::MAX
+    let input = format!("seeds: 1\n\na-to-b map:\n0 {} 1\n", max - 1);
+    let map_data = process_str_5(&input).unwrap();
+    assert_eq!(map_data.maps.map_value("a", "b", max - 1).unwrap(), 0);
+
+    // Seeds that only overflow when read as a range still work for part 1
+    let input = format!("seeds: {} 2\n\nseed-to-location map:\n0 0 1\n", max);
+    let map_data = process_str_5(&input).unwrap();
+    assert_eq!(find_lowest_seed_location(&map_data).unwrap(), 2);
+    assert!(matches!(
+        find_lowest_seed_range_location(&map_data),
+        Err(AocError::Unsolvable(_))
+    ));
+    assert_eq!(
+        map_data.maps.map_value("seed", "location", max).unwrap(),
+        max
+    );
+    assert!(matches!(
+        map_data.maps.map_value("location", "seed", max),
+        Err(AocError::Unsolvable(_))
+    ));
 }
 
 #[test]
@@ -172,22 +314,59 @@ fn test_day_6() {
     for (file_path, expected_products, expected_combined_products) in
         test_cases.iter()
     {
-        match process_file_6(file_path) {
-            Ok((time_vector, distance_vector)) => {
-                let (products, combined_products) =
-                    count_number_of_ways_to_beat_record(
-                        &time_vector,
-                        &distance_vector,
-                    );
-
-                assert_eq!(products, *expected_products);
-                assert_eq!(combined_products, *expected_combined_products);
-            }
-            Err(err) => eprintln!("Error reading file: {}", err),
-        }
+        let (time_vector, distance_vector) = process_file_6(file_path).unwrap();
+        let (products, combined_products) =
+            count_number_of_ways_to_beat_record(&time_vector, &distance_vector)
+                .unwrap();
+
+        assert_eq!(products, *expected_products);
+        assert_eq!(combined_products, *expected_combined_products);
     }
 }
 
+#[test]
+fn test_day_6_boundaries() {
+    let test_cases: [(u128, u128, u128); 6] = [
+        (7, 9, 4),
+        (30, 200, 9),
+        // Equalling the record is not enough
+        (4, 4, 0),
+        (4, 3, 1),
+        (0, 0, 0),
+        // Too long to square, every hold time but 0 and the full race wins
+        (u128::MAX, 0, u128::MAX - 1),
+    ];
+
+    for (time, distance, expected_count) in test_cases.iter() {
+        let (count, combined_count) =
+            count_number_of_ways_to_beat_record(&[*time], &[*distance])
+                .unwrap();
+
+        assert_eq!(count, *expected_count);
+        assert_eq!(combined_count, *expected_count);
+    }
+
+    let (_, combined_count) =
+        count_number_of_ways_to_beat_record(&[7, 15, 30], &[9, 40, 200])
+            .unwrap();
+    assert_eq!(combined_count, 71503);
+
+    // Only the combined race is too long, so part 1 still has an answer
+    let (times, distances) = ([u128::MAX, 1], [1, 1]);
+    assert_eq!(ways_to_beat_each_record(&times, &distances).unwrap(), 0);
+    assert!(matches!(
+        ways_to_beat_combined_record(&times, &distances),
+        Err(AocError::Unsolvable(_))
+    ));
+
+    // Every count fits but their product does not
+    let (times, distances) = ([u128::MAX, u128::MAX], [0, 0]);
+    assert!(matches!(
+        ways_to_beat_each_record(&times, &distances),
+        Err(AocError::Unsolvable(_))
+    ));
+}
+
 #[test]
 fn test_day_7() {
     let test_cases: [(&str, u32, u32); 2] = [
@@ -196,37 +375,225 @@ fn test_day_7() {
     ];
 
     for (file_path, expected_total, expected_joker_total) in test_cases.iter() {
-        let result_map = process_file_7(file_path);
-        let (total, joker_total) = calculate_total(&result_map);
+        let bids = process_file_7(file_path).unwrap();
+        let (total, joker_total) = calculate_total(&bids);
 
         assert_eq!(total, *expected_total);
         assert_eq!(joker_total, *expected_joker_total);
     }
 }
 
+#[test]
+fn test_day_7_hands() {
+    let test_cases = [
+        ("32T3K", HandType::OnePair, HandType::OnePair),
+        ("T55J5", HandType::ThreeOfAKind, HandType::FourOfAKind),
+        ("KK677", HandType::TwoPair, HandType::TwoPair),
+        ("KTJJT", HandType::TwoPair, HandType::FourOfAKind),
+        ("QQQJA", HandType::ThreeOfAKind, HandType::FourOfAKind),
+        ("23J45", HandType::HighCard, HandType::OnePair),
+        ("22J33", HandType::TwoPair, HandType::FullHouse),
+        ("JJJJJ", HandType::FiveOfAKind, HandType::FiveOfAKind),
+        ("JJJJ2", HandType::FourOfAKind, HandType::FiveOfAKind),
+        ("AAKKK", HandType::FullHouse, HandType::FullHouse),
+    ];
+
+    for (cards, standard, jokers) in test_cases {
+        let hand = Hand::parse(cards, Rules::Standard).unwrap();
+        assert_eq!(hand.to_string(), cards);
+        assert_eq!(hand.hand_type(), standard, "{}", cards);
+        assert_eq!(hand.with_rules(Rules::Jokers).hand_type(), jokers);
+    }
+
+    let hand = |cards, rules| Hand::parse(cards, rules).unwrap();
+    assert!(hand("KK677", Rules::Standard) > hand("KTJJT", Rules::Standard));
+    assert!(hand("KK677", Rules::Jokers) < hand("KTJJT", Rules::Jokers));
+    assert!(hand("JKKK2", Rules::Jokers) < hand("QQQQ2", Rules::Jokers));
+    assert!(hand("J2345", Rules::Jokers) < hand("2J345", Rules::Jokers));
+    assert!(hand("33332", Rules::Standard) > hand("2AAAA", Rules::Standard));
+
+    for cards in ["", "AAAA", "AAAAAA", "AAAA1"] {
+        assert_eq!(Hand::parse(cards, Rules::Standard), None, "{}", cards);
+    }
+}
+
+#[test]
+fn test_day_7_bids() {
+    // Identical hands keep their own bids, ranked in input order
+    let bids = process_str_7("32T3K 1\nKK677 10\n32T3K 2\n").unwrap();
+    let hands: Vec<(usize, String, u32)> = bids
+        .iter()
+        .map(|bid| (bid.line, bid.hand.to_string(), bid.bid))
+        .collect();
+    assert_eq!(
+        hands,
+        [
+            (1, "32T3K".to_string(), 1),
+            (2, "KK677".to_string(), 10),
+            (3, "32T3K".to_string(), 2)
+        ]
+    );
+
+    // Blank lines still count towards the line numbers
+    let lines: Vec<usize> = process_str_7("\n32T3K 1\n\nKK677 10\n")
+        .unwrap()
+        .iter()
+        .map(|bid| bid.line)
+        .collect();
+    assert_eq!(lines, [2, 4]);
+    assert_eq!(calculate_total(&bids), (1 + 2 * 2 + 3 * 10, 35));
+
+    let test_cases = [
+        ("32T3K 765\nKK6X7 28", 2, 4),
+        ("32T3K 765\n\n KK67 28", 3, 2),
+        ("32T3K 765\nKK6777 28", 2, 1),
+        ("32t3K 765", 1, 3),
+        ("32T3K", 1, 6),
+        ("32T3K 765 1", 1, 11),
+        ("32T3K -765", 1, 7),
+    ];
+
+    for (input, expected_line, expected_column) in test_cases {
+        match process_str_7(input) {
+            Err(AocError::Parse { line, column, .. }) => {
+                assert_eq!(
+                    (line, column),
+                    (expected_line, expected_column),
+                    "{:?}",
+                    input
+                );
+            }
+            other => panic!(
+                "expected a parse error for {:?}, got {:?}",
+                input, other
+            ),
+        }
+    }
+}
+
 #[test]
 fn test_day_8() {
-    let test_cases: [(&str, u64, u64); 3] = [
-        ("input/y2023/day_8_1.txt", 2, 2),
-        ("input/y2023/day_8_2.txt", 19099, 17099847107071),
-        ("input/y2023/day_8_3.txt", 0, 6),
+    let test_cases: [(&str, Option<u64>, u64); 3] = [
+        ("input/y2023/day_8_1.txt", Some(2), 2),
+        ("input/y2023/day_8_2.txt", Some(19099), 17099847107071),
+        // There is no AAA, so only part 2 has an answer
+        ("input/y2023/day_8_3.txt", None, 6),
     ];
 
     for (file_path, expected_steps, expected_steps_lcm) in test_cases.iter() {
-        match process_file_8(file_path) {
-            Ok(data) => {
-                let steps = traverse_graph(&data.graph, &data.instructions);
+        let data = process_file_8(file_path).unwrap();
+        let steps = traverse_graph(&data.network, &data.instructions);
+        match expected_steps {
+            Some(expected_steps) => assert_eq!(steps.unwrap(), *expected_steps),
+            None => assert!(matches!(steps, Err(AocError::Unsolvable(_)))),
+        }
+
+        let steps_lcm =
+            find_ghost_steps(&data.network, &data.instructions).unwrap();
+
+        assert_eq!(steps_lcm, *expected_steps_lcm);
+    }
+}
+
+#[test]
+fn test_day_8_unreachable() {
+    // ZZZ is a neighbour of AAA, but only ever turning left never gets there
+    let test_cases = [
+        ("L\n\nAAA = (AAA, ZZZ)\nZZZ = (ZZZ, ZZZ)\n", None),
+        (
+            "LR\n\nAAA = (BBB, ZZZ)\nBBB = (AAA, AAA)\nZZZ = (ZZZ, ZZZ)\n",
+            None,
+        ),
+        (
+            "LLR\n\nAAA = (BBB, ZZZ)\nBBB = (AAA, AAA)\nZZZ = (ZZZ, ZZZ)\n",
+            Some(3),
+        ),
+        ("L\n\nAAA = (BBB, BBB)\nBBB = (BBB, BBB)\n", None),
+    ];
+
+    for (input, expected_steps) in test_cases {
+        let data = process_str_8(input).unwrap();
+        let steps = traverse_graph(&data.network, &data.instructions);
+        match expected_steps {
+            Some(expected_steps) => assert_eq!(steps.unwrap(), expected_steps),
+            None => assert!(
+                matches!(steps, Err(AocError::Unsolvable(_))),
+                "{:?}",
+                input
+            ),
+        }
+    }
+}
+
+#[test]
+fn test_day_8_network() {
+    let data = process_file_8("input/y2023/day_8_1.txt").unwrap();
+    let network = &data.network;
+    let start = network.id("AAA").unwrap();
+
+    assert_eq!(network.len(), 7);
+    assert_eq!(network.name(start), "AAA");
+    assert_eq!(network.ids_ending_with('Z'), [network.id("ZZZ").unwrap()]);
+    assert_eq!(network.id("XXX"), None);
 
-                let steps_lcm = find_steps_lcm(&data.graph, &data.instructions);
+    let test_cases = [
+        ("LR\n\nAAA = (BBB, BBB)\nAAA = (BBB, BBB)\n", (4, 1)),
+        ("LR\n\nAAA = (BBB, CCC)\nBBB = (AAA, AAA)\n", (3, 13)),
+        ("LXR\n\nAAA = (AAA, AAA)\n", (1, 2)),
+        ("\n\nAAA = (AAA, AAA)\n", (1, 1)),
+    ];
 
-                assert_eq!(steps, *expected_steps);
-                assert_eq!(steps_lcm, *expected_steps_lcm);
+    for (input, expected_position) in test_cases.iter() {
+        match process_str_8(input) {
+            Err(AocError::Parse { line, column, .. }) => {
+                assert_eq!((line, column), *expected_position);
             }
-            Err(err) => eprintln!("Error reading file: {}", err),
+            other => panic!("expected a parse error, got {:?}", other),
         }
     }
 }
 
+#[test]
+fn test_day_8_ghost_cycles() {
+    let ghost_1 = "1A = (1Z, 1Z)\n1Z = (1B, 1B)\n1B = (1Z, 1Z)\n";
+    let ghost_2 = "2A = (2B, 2B)\n2B = (2C, 2C)\n2C = (2Z, 2Z)\n\
+                   2Z = (2B, 2B)\n";
+    let ghost_3 = "3A = (3B, 3B)\n3B = (3Z, 3Z)\n3Z = (3C, 3C)\n\
+                   3C = (3D, 3D)\n3D = (3E, 3E)\n3E = (3B, 3B)\n";
+    let even = "4A = (4B, 4B)\n4B = (4Z, 4Z)\n4Z = (4B, 4B)\n";
+    let once = "5A = (5Z, 5Z)\n5Z = (5B, 5B)\n5B = (5B, 5B)\n";
+    let always = "6A = (6Z, 6Z)\n6Z = (6Z, 6Z)\n";
+
+    let test_cases = [
+        // Odd times and multiples of 3, where the LCM would give 6
+        (vec![ghost_1, ghost_2], Some(3)),
+        // ... and 2 modulo 5
+        (vec![ghost_1, ghost_2, ghost_3], Some(27)),
+        // Odd and even times never meet
+        (vec![ghost_1, even], None),
+        // A hit before the ghost settles into its cycle
+        (vec![once, always], Some(1)),
+        (vec![once, ghost_2], None),
+    ];
+
+    for (ghosts, expected_steps) in test_cases.iter() {
+        let input = format!("L\n\n{}", ghosts.concat());
+        let data = process_str_8(&input).unwrap();
+
+        match find_ghost_steps(&data.network, &data.instructions) {
+            Ok(steps) => assert_eq!(Some(steps), *expected_steps),
+            Err(AocError::Unsolvable(_)) => assert_eq!(*expected_steps, None),
+            Err(err) => panic!("unexpected error: {}", err),
+        }
+    }
+
+    let data = process_str_8("L\n\nZZZ = (ZZZ, ZZZ)\n").unwrap();
+    assert!(matches!(
+        find_ghost_steps(&data.network, &data.instructions),
+        Err(AocError::Unsolvable(_))
+    ));
+}
+
 #[test]
 fn test_day_9() {
     let test_cases: [(&str, i32, i32); 2] = [
@@ -236,13 +603,61 @@ fn test_day_9() {
 
     for (file_path, expected_end_sums, expected_start_sums) in test_cases.iter()
     {
-        let (start_sums, end_sums) = process_file_9(file_path);
+        let (start_sums, end_sums) = process_file_9(file_path).unwrap();
 
         assert_eq!(end_sums, *expected_end_sums);
         assert_eq!(start_sums, *expected_start_sums);
     }
 }
 
+#[test]
+fn test_day_9_from_str() {
+    let test_cases: [(&str, i32, i32); 5] = [
+        ("0 3 6 9 12 15\n1 3 6 10 15 21\n10 13 16 21 30 45\n", 114, 2),
+        ("10 13 16 21 30 45", 68, 5),
+        // A single value is a constant history
+        ("5\n\n-3\n", 2, 2),
+        ("  \n", 0, 0),
+        ("", 0, 0),
+    ];
+
+    for (input, expected_end_sums, expected_start_sums) in test_cases.iter() {
+        let (start_sums, end_sums) = process_str_9(input).unwrap();
+
+        assert_eq!(end_sums, *expected_end_sums);
+        assert_eq!(start_sums, *expected_start_sums);
+    }
+}
+
+#[test]
+fn test_day_9_parse_error() {
+    match process_str_9("0 3 6\n1 x 6\n") {
+        Err(AocError::Parse { line, column, .. }) => {
+            assert_eq!((line, column), (2, 3));
+        }
+        other => panic!("expected a parse error, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_day_9_sum_overflow() {
+    // Each history fits on its own but their sum does not
+    let result = process_str_9("2000000000\n2000000000\n");
+    assert!(matches!(result, Err(AocError::Unsolvable(_))));
+
+    // Only the value before the history overflows, so part 1 still works
+    let histories =
+        read_histories("-2147483648 -2147483647".as_bytes()).unwrap();
+    assert_eq!(
+        sum_extrapolated(&histories, Side::After).unwrap(),
+        -2147483646
+    );
+    assert!(matches!(
+        sum_extrapolated(&histories, Side::Before),
+        Err(AocError::Unsolvable(_))
+    ));
+}
+
 #[test]
 fn test_day_10() {
     let test_cases: [(&str, i32, i32); 6] = [
@@ -257,13 +672,62 @@ fn test_day_10() {
     for (file_path, expected_steps, expected_enclosed_count) in
         test_cases.iter()
     {
-        let (steps, enclosed_count) = process_file_10(file_path);
+        let (steps, enclosed_count) = process_file_10(file_path).unwrap();
 
         assert_eq!(steps, *expected_steps);
         assert_eq!(enclosed_count, *expected_enclosed_count);
     }
 }
 
+#[test]
+fn test_day_10_missing_start() {
+    let result = process_str_10(".....\n.F-7.\n.|.|.\n.L-J.\n.....\n");
+
+    assert!(matches!(result, Err(AocError::MissingStart('S'))));
+}
+
+#[test]
+fn test_day_10_resolve_start() {
+    let test_cases = [
+        (".....\n.S-7.\n.|.|.\n.L-J.\n.....", (1, 1), 'F'),
+        ("..F7.\n.FJ|.\nSJ.L7\n|F--J\nLJ...", (0, 2), 'F'),
+        ("-L|F7\n7S-7|\nL|7||\n-L-J|\nL|-JF", (1, 1), 'F'),
+        (".|.\n-S.\n...", (1, 1), 'J'),
+        // Stray pipes facing the start are not part of its loop
+        (".|...\n-S-7.\n.|.|.\n.L-J.\n.....", (1, 1), 'F'),
+        (".....\n.F-S-\n.|.|.\n.L-J.\n.....", (3, 1), '7'),
+    ];
+
+    for (input, expected_start, expected_pipe) in test_cases.iter() {
+        let maze = read_maze(input.as_bytes()).unwrap();
+
+        assert_eq!(maze.start, *expected_start);
+        assert_eq!(maze.pipes[*expected_start].to_char(), *expected_pipe);
+        assert_eq!(
+            maze.pipes.to_string(),
+            input.replace('S', &expected_pipe.to_string())
+        );
+    }
+
+    let test_cases = [(".S.\n.S.\n", 2, 2), (".S.\n.X.\n", 2, 2)];
+    for (input, expected_line, expected_column) in test_cases {
+        match process_str_10(input) {
+            Err(AocError::Parse { line, column, .. }) => {
+                assert_eq!((line, column), (expected_line, expected_column));
+            }
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+    }
+
+    let (steps, enclosed) =
+        process_str_10(".|...\n-S-7.\n.|.|.\n.L-J.\n.....").unwrap();
+    assert_eq!((steps, enclosed), (4, 1));
+
+    // Three pipes lead into the start, but no two of them close a loop
+    let result = process_str_10(".|.\n-S-\n...\n");
+    assert!(matches!(result, Err(AocError::Unsolvable(_))));
+}
+
 #[test]
 fn test_day_11() {
     let test_cases: [(&str, usize, usize); 2] = [
@@ -274,7 +738,7 @@ fn test_day_11() {
     for (file_path, expected_sum_length, expected_sum_length_n) in
         test_cases.iter()
     {
-        let galaxies_map = process_file_11(file_path);
+        let galaxies_map = process_file_11(file_path).unwrap();
 
         let sum_length = get_sum_length(&galaxies_map);
 
@@ -283,49 +747,207 @@ fn test_day_11() {
         assert_eq!(sum_length, *expected_sum_length);
         assert_eq!(sum_length_n, *expected_sum_length_n);
     }
+
+    // The empty column between the galaxies can also shrink away
+    let galaxies_map = process_str_11("#.#\n").unwrap();
+    for (n, expected_sum_length_n) in [(0, 1), (1, 2), (2, 3), (10, 11)] {
+        assert_eq!(get_sum_length_n(&galaxies_map, n), expected_sum_length_n);
+    }
+
+    let galaxies_map = process_str_11("...\n...\n").unwrap();
+    assert_eq!(get_sum_length(&galaxies_map), 0);
+    assert_eq!(get_sum_length_n(&galaxies_map, 0), 0);
 }
 
 #[test]
 fn test_day_12() {
-    let test_cases: [(&str, usize, usize); 2] = [
-        ("input/y2023/day_12_1.txt", 21, 1),
-        ("input/y2023/day_12_2.txt", 7221, 1),
+    let test_cases: [(&str, u64, u64); 2] = [
+        ("input/y2023/day_12_1.txt", 21, 525152),
+        ("input/y2023/day_12_2.txt", 7221, 7139671893722),
     ];
 
-    for (file_path, expected_sum, _) in test_cases.iter() {
-        let sum = process_file_12(file_path);
+    for (file_path, expected_sum, expected_unfolded_sum) in test_cases.iter() {
+        let rows = process_file_12(file_path).unwrap();
 
-        assert_eq!(sum, *expected_sum);
+        assert_eq!(sum_arrangements(&rows).unwrap(), *expected_sum);
+        assert_eq!(
+            sum_unfolded_arrangements(&rows).unwrap(),
+            *expected_unfolded_sum
+        );
+    }
+}
+
+#[test]
+fn test_day_12_count_arrangements() {
+    let test_cases: [(&str, &[usize], u64); 10] = [
+        ("???.###", &[1, 1, 3], 1),
+        (".??..??...?##.", &[1, 1, 3], 4),
+        ("?###????????", &[3, 2, 1], 10),
+        ("????", &[], 1),
+        ("#", &[], 0),
+        ("", &[1], 0),
+        ("##?", &[2], 1),
+        // Groups longer than the records are ruled out before counting
+        ("???", &[1_000_000_000_000], 0),
+        ("???", &[usize::MAX, 1], 0),
+        ("???", &[1, 2], 0),
+    ];
+
+    for (records, groups, expected_count) in test_cases.iter() {
+        assert_eq!(
+            count_arrangements(records, groups).unwrap(),
+            *expected_count
+        );
     }
 }
 
 #[test]
 fn test_day_13() {
     let test_cases: [(&str, usize, usize); 2] = [
-        ("input/y2023/day_13_1.txt", 405, 1),
-        ("input/y2023/day_13_2.txt", 34772, 1),
+        ("input/y2023/day_13_1.txt", 405, 400),
+        ("input/y2023/day_13_2.txt", 34772, 35554),
     ];
 
-    for (file_path, expected_sum, _) in test_cases.iter() {
-        let result = process_file_13(file_path);
-        let sum = get_sum(&result);
+    for (file_path, expected_sum, expected_smudged_sum) in test_cases.iter() {
+        let blocks = process_file_13(file_path).unwrap();
 
-        assert_eq!(sum, *expected_sum);
+        assert_eq!(get_sum(&blocks, 0).unwrap(), *expected_sum);
+        assert_eq!(get_sum(&blocks, 1).unwrap(), *expected_smudged_sum);
     }
+
+    // Neither rows nor columns mirror each other
+    let blocks = process_str_13("#.\n..\n").unwrap();
+    assert_eq!(blocks[0].summary(0), None);
+    assert!(matches!(get_sum(&blocks, 0), Err(AocError::Unsolvable(_))));
+}
+
+#[test]
+fn test_day_13_reflections() {
+    let blocks = process_file_13("input/y2023/day_13_1.txt").unwrap();
+    let reflections: Vec<_> = blocks
+        .iter()
+        .flat_map(|block| {
+            [0, 1].map(|smudges| {
+                (
+                    block.horizontal_reflection(smudges),
+                    block.vertical_reflection(smudges),
+                )
+            })
+        })
+        .collect();
+
+    assert_eq!(
+        reflections,
+        [
+            (None, Some(5)),
+            (Some(3), None),
+            (Some(4), None),
+            (Some(1), None)
+        ]
+    );
+
+    let test_cases: [(&[u64], u32, Option<usize>); 6] = [
+        (&[0b101, 0b101], 0, Some(1)),
+        (&[0b101, 0b100], 0, None),
+        (&[0b101, 0b100], 1, Some(1)),
+        // Lines beyond the shorter side are not compared
+        (&[0b111, 0b001, 0b010, 0b010], 0, Some(3)),
+        (&[0b111, 0b001, 0b010, 0b010], 2, Some(1)),
+        (&[0b1], 0, None),
+    ];
+
+    for (lines, smudges, expected_reflection) in test_cases.iter() {
+        assert_eq!(find_reflection(lines, *smudges), *expected_reflection);
+    }
+
+    let result = process_str_13(&format!("{}\n", "#".repeat(65)));
+    assert!(matches!(result, Err(AocError::Parse { line: 1, .. })));
 }
 
 #[test]
 fn test_day_14() {
     let test_cases: [(&str, usize, usize); 2] = [
-        ("input/y2023/day_14_1.txt", 136, 1),
-        ("input/y2023/day_14_2.txt", 113456, 1),
+        ("input/y2023/day_14_1.txt", 136, 64),
+        ("input/y2023/day_14_2.txt", 113456, 118747),
     ];
 
-    for (file_path, expected_total_load, _) in test_cases.iter() {
-        let mirror = process_file_14(file_path);
-        let tilted_north = tilt_north(&mirror);
-        let total_load = measure_load(&tilted_north);
+    for (file_path, expected_total_load, expected_cycled_load) in
+        test_cases.iter()
+    {
+        let platform = process_file_14(file_path).unwrap();
+        let total_load = measure_north_load(&platform);
+        let cycled_load = measure_load_after(&platform, 1_000_000_000);
 
         assert_eq!(total_load, *expected_total_load);
+        assert_eq!(cycled_load, *expected_cycled_load);
+    }
+}
+
+#[test]
+fn test_day_14_tilt() {
+    let mut platform = process_str_14("O.#.O\n.O..O\n#.O.#\n..O.#\n").unwrap();
+    let test_cases = [
+        (Tilt::North, "OO#.O\n..O.O\n#.O.#\n....#"),
+        (Tilt::West, "OO#O.\nOO...\n#O..#\n....#"),
+        (Tilt::South, "O.#..\nOO...\n#O..#\n.O.O#"),
+        (Tilt::East, ".O#..\n...OO\n#..O#\n..OO#"),
+    ];
+
+    for (tilt, expected) in test_cases {
+        platform.tilt(tilt);
+        assert_eq!(platform.to_string(), expected, "after {:?}", tilt);
+    }
+
+    let mut platform = process_file_14("input/y2023/day_14_1.txt").unwrap();
+    platform.spin_cycle();
+    assert_eq!(
+        platform.to_string(),
+        ".....#....\n\
+         ....#...O#\n\
+         ...OO##...\n\
+         .OO#......\n\
+         .....OOO#.\n\
+         .O#...O#.#\n\
+         ....O#....\n\
+         ......OOOO\n\
+         #...O###..\n\
+         #..OO#...."
+    );
+
+    assert!(matches!(
+        process_str_14(&".".repeat(129)),
+        Err(AocError::Parse {
+            line: 1,
+            column: 129,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn test_day_15() {
+    let test_cases: [(&str, u32, usize); 2] = [
+        ("input/y2023/day_15_1.txt", 1320, 145),
+        ("input/y2023/day_15_2.txt", 511257, 239484),
+    ];
+
+    assert_eq!(hash("HASH"), 52);
+
+    for (file_path, expected_sum, expected_focusing_power) in test_cases.iter()
+    {
+        let steps = process_file_15(file_path).unwrap();
+
+        assert_eq!(sum_of_hashes(&steps), *expected_sum);
+        assert_eq!(focusing_power(&steps), *expected_focusing_power);
+    }
+}
+
+#[test]
+fn test_day_15_parse_error() {
+    match process_str_15("rn=1,cm-,qp") {
+        Err(AocError::Parse { line, column, .. }) => {
+            assert_eq!((line, column), (1, 10));
+        }
+        other => panic!("expected a parse error, got {:?}", other),
     }
 }